@@ -952,12 +952,27 @@ impl FragmentDisplayListBuilding for Fragment {
                         );
                     }
                 },
+                Either::Second(Image::ConicGradient(_)) => {
+                    // TODO: Implement `conic-gradient`
+                },
+                Either::Second(Image::CrossFade(_)) => {
+                    // TODO: Implement `cross-fade`
+                },
+                Either::Second(Image::ImageSet(_)) => {
+                    // TODO: Implement `image-set`
+                },
                 Either::Second(Image::Rect(_)) => {
                     // TODO: Implement `-moz-image-rect`
                 },
                 Either::Second(Image::Element(_)) => {
                     // TODO: Implement `-moz-element`
                 },
+                Either::Second(Image::Image(_)) => {
+                    // TODO: Implement `image()`
+                },
+                Either::Second(Image::Attr(_)) => {
+                    // TODO: Implement `attr()` images
+                },
             }
         }
     }
@@ -1118,7 +1133,7 @@ impl FragmentDisplayListBuilding for Fragment {
                     let gradient = convert_linear_gradient(
                         placement.tile_size,
                         &gradient.items[..],
-                        angle_or_corner,
+                        angle_or_corner.to_modern(gradient.compat_mode),
                         gradient.repeating,
                     );
                     DisplayItem::Gradient(Box::new(GradientDisplayItem {
@@ -1300,7 +1315,7 @@ impl FragmentDisplayListBuilding for Fragment {
                     gradient: convert_linear_gradient(
                         bounds.size,
                         &gradient.items[..],
-                        angle_or_corner,
+                        angle_or_corner.to_modern(gradient.compat_mode),
                         gradient.repeating,
                     ),
                     outset: outset_layout,
@@ -1324,6 +1339,18 @@ impl FragmentDisplayListBuilding for Fragment {
                         build_image_border_details(image, border_style_struct, outset_layout)
                     })
             },
+            Either::Second(Image::ConicGradient(..)) => {
+                // TODO: Handle border-image with `conic-gradient`.
+                None
+            },
+            Either::Second(Image::CrossFade(..)) => {
+                // TODO: Handle border-image with `cross-fade`.
+                None
+            },
+            Either::Second(Image::ImageSet(..)) => {
+                // TODO: Handle border-image with `image-set`.
+                None
+            },
             Either::Second(Image::Rect(..)) => {
                 // TODO: Handle border-image with `-moz-image-rect`.
                 None
@@ -1332,6 +1359,14 @@ impl FragmentDisplayListBuilding for Fragment {
                 // TODO: Handle border-image with `-moz-element`.
                 None
             },
+            Either::Second(Image::Image(..)) => {
+                // TODO: Handle border-image with `image()`.
+                None
+            },
+            Either::Second(Image::Attr(..)) => {
+                // TODO: Handle border-image with `attr()`.
+                None
+            },
             Either::Second(Image::Url(ref image_url)) => image_url
                 .url()
                 .and_then(|url| {
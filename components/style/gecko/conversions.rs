@@ -144,6 +144,11 @@ impl nsStyleImage {
     pub fn set(&mut self, image: Image) {
         match image {
             GenericImage::Gradient(boxed_gradient) => self.set_gradient(*boxed_gradient),
+            GenericImage::ConicGradient(..) => unreachable!("Conic gradients are not parsed in Gecko"),
+            GenericImage::CrossFade(..) => unreachable!("cross-fade() is not parsed in Gecko"),
+            GenericImage::ImageSet(..) => unreachable!("image-set() is not parsed in Gecko"),
+            GenericImage::Image(..) => unreachable!("image() is not parsed in Gecko"),
+            GenericImage::Attr(..) => unreachable!("attr() images are not parsed in Gecko"),
             GenericImage::Url(ref url) => unsafe {
                 bindings::Gecko_SetLayerImageImageValue(self, url.0.image_value.get());
             },
@@ -621,6 +626,7 @@ impl nsStyleImage {
             repeating: gecko_gradient.mRepeating,
             kind,
             compat_mode,
+            color_interpolation: None,
         })
     }
 }
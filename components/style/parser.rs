@@ -5,7 +5,7 @@
 //! The context within which CSS code is parsed.
 
 use context::QuirksMode;
-use cssparser::{Parser, SourceLocation, UnicodeRange};
+use cssparser::{Parser, ParserInput, SourceLocation, UnicodeRange};
 use error_reporting::{ContextualParseError, ParseErrorReporter};
 use style_traits::{OneOrMoreSeparated, ParseError, ParsingMode, Separator};
 use stylesheets::{CssRuleType, Namespaces, Origin, UrlExtraData};
@@ -162,6 +162,40 @@ pub trait Parse: Sized {
         context: &ParserContext,
         input: &mut Parser<'i, 't>,
     ) -> Result<Self, ParseError<'i>>;
+
+    /// Parse a value of this type from a string.
+    ///
+    /// Returns an error on failure, or if there is any input left after the
+    /// value.
+    fn parse_str<'i>(context: &ParserContext, input: &'i str) -> Result<Self, ParseError<'i>> {
+        let mut input = ParserInput::new(input);
+        let mut parser = Parser::new(&mut input);
+        parser.parse_entirely(|i| Self::parse(context, i))
+    }
+}
+
+/// Parses a standalone value of type `T` from `input`, as an author style
+/// declaration would, without quirks.
+///
+/// This is the entry point for parsing arbitrary untrusted strings, like
+/// fuzzers do. Parsing a value, and computing and serializing whatever this
+/// returns, must not panic for any input. Other operations on the parsed
+/// value don't give that guarantee, e.g. scaling a `calc()` length panics.
+pub fn parse_value_safely<'i, T>(
+    url_data: &UrlExtraData,
+    input: &'i str,
+) -> Result<T, ParseError<'i>>
+where
+    T: Parse,
+{
+    let context = ParserContext::new(
+        Origin::Author,
+        url_data,
+        Some(CssRuleType::Style),
+        ParsingMode::DEFAULT,
+        QuirksMode::NoQuirks,
+    );
+    T::parse_str(&context, input)
 }
 
 impl<T> Parse for Vec<T>
@@ -906,6 +906,12 @@ impl ToAnimatedZero for Visibility {
 impl Animate for CalcLengthOrPercentage {
     #[inline]
     fn animate(&self, other: &Self, procedure: Procedure) -> Result<Self, ()> {
+        // A min() or max() of lengths and percentages isn't a sum that can be
+        // interpolated, so it animates discretely.
+        if self.has_deferred_min_max() || other.has_deferred_min_max() {
+            return Err(());
+        }
+
         let animate_percentage_half = |this: Option<Percentage>, other: Option<Percentage>| {
             if this.is_none() && other.is_none() {
                 return Ok(None);
@@ -50,6 +50,16 @@ impl CssUrl {
         }
     }
 
+    /// Try to parse a URL from a string value that is a valid CSS token for a
+    /// URL, failing if it can't be resolved against the stylesheet's URL.
+    pub fn try_parse_from_string(url: String, context: &ParserContext) -> Result<Self, ()> {
+        let resolved = context.url_data.join(&url).map_err(|_| ())?;
+        Ok(CssUrl {
+            original: Some(Arc::new(url)),
+            resolved: Some(resolved),
+        })
+    }
+
     /// Returns true if the URL is definitely invalid. For Servo URLs, we can
     /// use its |resolved| status.
     pub fn is_invalid(&self) -> bool {
@@ -59,12 +69,10 @@ impl CssUrl {
     /// Returns true if this URL looks like a fragment.
     /// See https://drafts.csswg.org/css-values/#local-urls
     ///
-    /// Since Servo currently stores resolved URLs, this is hard to implement. We
-    /// either need to change servo to lazily resolve (like Gecko), or note this
-    /// information in the tokenizer.
+    /// This relies on the original serialization, so URLs inserted in the
+    /// cascade directly are never considered fragments.
     pub fn is_fragment(&self) -> bool {
-        error!("Can't determine whether the url is a fragment.");
-        false
+        self.original.as_ref().map_or(false, |original| original.starts_with('#'))
     }
 
     /// Returns the resolved url if it was valid.
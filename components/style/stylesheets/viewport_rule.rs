@@ -765,6 +765,9 @@ impl MaybeNew for ViewportConstraints {
                             LengthOrPercentageOrAuto::Calc(ref calc) => calc.to_computed_value(
                                 &context,
                             ).to_used_value(Some(initial_viewport.$dimension)),
+                            LengthOrPercentageOrAuto::MinMax(ref min_max) => min_max
+                                .to_computed_value(&context)
+                                .to_used_value(Some(initial_viewport.$dimension)),
                         },
                         ViewportLength::ExtendToZoom => {
                             // $extend_to will be 'None' if 'extend-to-zoom' is 'auto'
@@ -7,7 +7,7 @@
 use num_traits::Zero;
 use std::{f32, f64};
 use std::f64::consts::PI;
-use std::ops::Add;
+use std::ops::{Add, Div, Mul, Sub};
 use values::CSSFloat;
 use values::animated::{Animate, Procedure};
 use values::distance::{ComputeSquaredDistance, SquaredDistance};
@@ -70,6 +70,30 @@ impl Angle {
         self.radians() * 360. / (2. * PI)
     }
 
+    /// Returns an equivalent angle in the `[0, 2π)` range, in the same unit.
+    pub fn normalized(&self) -> Self {
+        fn wrap(value: CSSFloat, full: CSSFloat) -> CSSFloat {
+            let value = value % full;
+            if value >= 0. {
+                return value;
+            }
+            // Tiny negative values round up to `full` when it's added back.
+            let value = value + full;
+            if value >= full {
+                0.
+            } else {
+                value
+            }
+        }
+
+        match *self {
+            Angle::Deg(val) => Angle::Deg(wrap(val, 360.)),
+            Angle::Grad(val) => Angle::Grad(wrap(val, 400.)),
+            Angle::Turn(val) => Angle::Turn(wrap(val, 1.)),
+            Angle::Rad(val) => Angle::Rad(wrap(val, 2. * f32::consts::PI)),
+        }
+    }
+
     /// <https://drafts.csswg.org/css-transitions/#animtype-number>
     #[inline]
     fn animate_fallback(&self, other: &Self, procedure: Procedure) -> Result<Self, ()> {
@@ -99,6 +123,49 @@ impl Add for Angle {
     }
 }
 
+impl Sub for Angle {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        match (self, rhs) {
+            (Angle::Deg(x), Angle::Deg(y)) => Angle::Deg(x - y),
+            (Angle::Grad(x), Angle::Grad(y)) => Angle::Grad(x - y),
+            (Angle::Turn(x), Angle::Turn(y)) => Angle::Turn(x - y),
+            (Angle::Rad(x), Angle::Rad(y)) => Angle::Rad(x - y),
+            _ => Angle::from_radians(self.radians() - rhs.radians()),
+        }
+    }
+}
+
+impl Mul<CSSFloat> for Angle {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: CSSFloat) -> Self {
+        match self {
+            Angle::Deg(val) => Angle::Deg(val * rhs),
+            Angle::Grad(val) => Angle::Grad(val * rhs),
+            Angle::Turn(val) => Angle::Turn(val * rhs),
+            Angle::Rad(val) => Angle::Rad(val * rhs),
+        }
+    }
+}
+
+impl Div<CSSFloat> for Angle {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, rhs: CSSFloat) -> Self {
+        match self {
+            Angle::Deg(val) => Angle::Deg(val / rhs),
+            Angle::Grad(val) => Angle::Grad(val / rhs),
+            Angle::Turn(val) => Angle::Turn(val / rhs),
+            Angle::Rad(val) => Angle::Rad(val / rhs),
+        }
+    }
+}
+
 impl Zero for Angle {
     #[inline]
     fn zero() -> Self {
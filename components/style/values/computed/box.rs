@@ -7,7 +7,9 @@
 use values::computed::Number;
 use values::computed::length::{LengthOrPercentage, NonNegativeLength};
 use values::generics::box_::AnimationIterationCount as GenericAnimationIterationCount;
+use values::generics::box_::AspectRatio as GenericAspectRatio;
 use values::generics::box_::Perspective as GenericPerspective;
+use values::generics::box_::Ratio as GenericRatio;
 use values::generics::box_::VerticalAlign as GenericVerticalAlign;
 
 pub use values::specified::box_::{AnimationName, Contain, Display, OverflowClipBox};
@@ -29,3 +31,9 @@ impl AnimationIterationCount {
 
 /// A computed value for the `perspective` property.
 pub type Perspective = GenericPerspective<NonNegativeLength>;
+
+/// A computed `<ratio>` value.
+pub type Ratio = GenericRatio<Number>;
+
+/// A computed value for the `aspect-ratio` property.
+pub type AspectRatio = GenericAspectRatio<Number>;
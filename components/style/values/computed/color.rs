@@ -10,10 +10,13 @@ use style_traits::{CssWriter, ToCss};
 use values::animated::ToAnimatedValue;
 use values::animated::color::{Color as AnimatedColor, RGBA as AnimatedRGBA};
 
+pub use values::specified::color::{ColorInterpolationMethod, ColorSpace, HueInterpolationMethod};
+
 /// This struct represents a combined color from a numeric color and
 /// the current foreground color (currentcolor keyword).
 /// Conceptually, the formula is "color * (1 - p) + currentcolor * p"
 /// where p is foreground_ratio.
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Copy, Debug, MallocSizeOf)]
 pub struct Color {
     /// RGBA color.
@@ -61,6 +64,20 @@ impl Color {
         self.foreground_ratio == u8::max_value()
     }
 
+    /// Returns the alpha of this color, between 0 and 1, once combined with
+    /// the given foreground color.
+    #[inline]
+    pub fn alpha(&self, fg_color: RGBA) -> f32 {
+        self.to_rgba(fg_color).alpha_f32()
+    }
+
+    /// Whether this color is fully opaque once combined with the given
+    /// foreground color, so that painting it doesn't need blending.
+    #[inline]
+    pub fn is_opaque(&self, fg_color: RGBA) -> bool {
+        self.to_rgba(fg_color).alpha == u8::max_value()
+    }
+
     /// Combine this complex color with the given foreground color into
     /// a numeric RGBA color. It currently uses linear blending.
     pub fn to_rgba(&self, fg_color: RGBA) -> RGBA {
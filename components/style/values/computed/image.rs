@@ -12,13 +12,13 @@ use std::f32::consts::PI;
 use std::fmt::{self, Write};
 use style_traits::{CssWriter, ToCss};
 use values::{Either, None_};
-use values::computed::{Angle, Context};
+use values::computed::{Angle, Context, Percentage, Resolution};
 use values::computed::{Length, LengthOrPercentage, NumberOrPercentage, ToComputedValue};
-#[cfg(feature = "gecko")]
-use values::computed::Percentage;
 use values::computed::position::Position;
 use values::computed::url::ComputedImageUrl;
 use values::generics::image::{self as generic, CompatMode};
+use values::specified::image::ConicGradient as SpecifiedConicGradient;
+use values::specified::image::Gradient as SpecifiedGradient;
 use values::specified::image::LineDirection as SpecifiedLineDirection;
 use values::specified::position::{X, Y};
 
@@ -27,18 +27,43 @@ pub type ImageLayer = Either<None_, Image>;
 
 /// Computed values for an image according to CSS-IMAGES.
 /// <https://drafts.csswg.org/css-images/#image-values>
-pub type Image = generic::Image<Gradient, MozImageRect, ComputedImageUrl>;
+pub type Image = generic::Image<
+    Gradient,
+    ConicGradient,
+    MozImageRect,
+    ComputedImageUrl,
+    Percentage,
+    Resolution,
+    RGBA,
+>;
 
 /// Computed values for a CSS gradient.
 /// <https://drafts.csswg.org/css-images/#gradients>
 pub type Gradient =
     generic::Gradient<LineDirection, Length, LengthOrPercentage, Position, RGBA, Angle>;
 
+/// Computed values for a CSS conic gradient.
+/// <https://drafts.csswg.org/css-images-4/#conic-gradients>
+pub type ConicGradient = generic::ConicGradient<Angle, Position, RGBA, Either<Angle, Percentage>>;
+
+/// Computed values for a `cross-fade()` image.
+pub type CrossFade = generic::CrossFade<Image, Percentage>;
+
+/// Computed values for an `image-set()` image.
+///
+/// All the candidates are kept, so that the image can be selected at paint
+/// time.
+pub type ImageSet = generic::ImageSet<Image, Resolution>;
+
+/// Computed values for an `image()` image.
+pub type ImageFunction = generic::ImageFunction<ComputedImageUrl, RGBA>;
+
 /// A computed gradient kind.
 pub type GradientKind =
     generic::GradientKind<LineDirection, Length, LengthOrPercentage, Position, Angle>;
 
 /// A computed gradient line direction.
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Copy, Debug, MallocSizeOf, PartialEq)]
 pub enum LineDirection {
     /// An angle.
@@ -66,6 +91,41 @@ pub type ColorStop = generic::ColorStop<RGBA, LengthOrPercentage>;
 /// Computed values for `-moz-image-rect(...)`.
 pub type MozImageRect = generic::MozImageRect<NumberOrPercentage, ComputedImageUrl>;
 
+impl LineDirection {
+    /// Returns the equivalent direction in the modern `linear-gradient()`
+    /// syntax, for a direction specified with the given compatibility mode.
+    ///
+    /// In the prefixed syntaxes, side and corner keywords name where the
+    /// gradient starts rather than where it ends, and angles are measured
+    /// counter-clockwise from the right rather than clockwise from the top.
+    pub fn to_modern(self, compat_mode: CompatMode) -> Self {
+        if compat_mode == CompatMode::Modern {
+            return self;
+        }
+        match self {
+            LineDirection::Angle(angle) => LineDirection::Angle(Angle::Deg(90. - angle.degrees())),
+            LineDirection::Horizontal(x) => LineDirection::Horizontal(x.opposite()),
+            LineDirection::Vertical(y) => LineDirection::Vertical(y.opposite()),
+            LineDirection::Corner(x, y) => LineDirection::Corner(x.opposite(), y.opposite()),
+            #[cfg(feature = "gecko")]
+            direction @ LineDirection::MozPosition(..) => direction,
+        }
+    }
+
+    /// Returns the same direction, with its angle, if any, normalized to the
+    /// `[0, 2π)` range.
+    ///
+    /// The gradient line only depends on the sine and cosine of the angle, so
+    /// this doesn't change how a gradient paints, and serialization keeps the
+    /// angle as specified.
+    pub fn normalized(self) -> Self {
+        match self {
+            LineDirection::Angle(angle) => LineDirection::Angle(angle.normalized()),
+            direction => direction,
+        }
+    }
+}
+
 impl generic::LineDirection for LineDirection {
     fn points_downwards(&self, compat_mode: CompatMode) -> bool {
         match *self {
@@ -172,3 +232,188 @@ impl ToComputedValue for SpecifiedLineDirection {
         }
     }
 }
+
+impl ToComputedValue for SpecifiedGradient {
+    type ComputedValue = Gradient;
+
+    fn to_computed_value(&self, context: &Context) -> Self::ComputedValue {
+        Gradient {
+            kind: self.kind.to_computed_value(context),
+            items: compute_color_stops(&self.items, context),
+            repeating: self.repeating,
+            compat_mode: self.compat_mode,
+            color_interpolation: self.color_interpolation,
+        }
+    }
+
+    fn from_computed_value(computed: &Self::ComputedValue) -> Self {
+        SpecifiedGradient {
+            kind: ToComputedValue::from_computed_value(&computed.kind),
+            items: ToComputedValue::from_computed_value(&computed.items),
+            repeating: computed.repeating,
+            compat_mode: computed.compat_mode,
+            color_interpolation: computed.color_interpolation,
+        }
+    }
+}
+
+impl ToComputedValue for SpecifiedConicGradient {
+    type ComputedValue = ConicGradient;
+
+    fn to_computed_value(&self, context: &Context) -> Self::ComputedValue {
+        ConicGradient {
+            angle: self.angle.to_computed_value(context),
+            position: self.position.to_computed_value(context),
+            items: compute_color_stops(&self.items, context),
+            repeating: self.repeating,
+        }
+    }
+
+    fn from_computed_value(computed: &Self::ComputedValue) -> Self {
+        SpecifiedConicGradient {
+            angle: ToComputedValue::from_computed_value(&computed.angle),
+            position: ToComputedValue::from_computed_value(&computed.position),
+            items: ToComputedValue::from_computed_value(&computed.items),
+            repeating: computed.repeating,
+        }
+    }
+}
+
+/// A computed color stop position, which the color stop fixup compares with
+/// the positions before it.
+pub trait ColorStopPosition: Clone {
+    /// Whether this position can be compared with `other`, which is the case
+    /// when both of them are of the same kind, like two lengths.
+    fn is_comparable(&self, other: &Self) -> bool;
+
+    /// Whether this position is before `other`, which it's comparable with.
+    fn is_less_than(&self, other: &Self) -> bool;
+}
+
+impl ColorStopPosition for LengthOrPercentage {
+    fn is_comparable(&self, other: &Self) -> bool {
+        match (*self, *other) {
+            (LengthOrPercentage::Length(..), LengthOrPercentage::Length(..)) |
+            (LengthOrPercentage::Percentage(..), LengthOrPercentage::Percentage(..)) => true,
+            _ => false,
+        }
+    }
+
+    fn is_less_than(&self, other: &Self) -> bool {
+        match (*self, *other) {
+            (LengthOrPercentage::Length(a), LengthOrPercentage::Length(b)) => a < b,
+            (LengthOrPercentage::Percentage(a), LengthOrPercentage::Percentage(b)) => a.0 < b.0,
+            _ => false,
+        }
+    }
+}
+
+impl ColorStopPosition for Either<Angle, Percentage> {
+    fn is_comparable(&self, other: &Self) -> bool {
+        match (*self, *other) {
+            (Either::First(..), Either::First(..)) |
+            (Either::Second(..), Either::Second(..)) => true,
+            _ => false,
+        }
+    }
+
+    fn is_less_than(&self, other: &Self) -> bool {
+        match (*self, *other) {
+            (Either::First(a), Either::First(b)) => a.radians() < b.radians(),
+            (Either::Second(a), Either::Second(b)) => a.0 < b.0,
+            _ => false,
+        }
+    }
+}
+
+/// Computes the color stops and interpolation hints of a gradient.
+///
+/// This is shared by linear, radial and conic gradients, so that they all get
+/// the same stop computation, including the fixup of out-of-order positions
+/// and the removal of redundant stops.
+pub fn compute_color_stops<S, P>(
+    items: &[S],
+    context: &Context,
+) -> Vec<generic::GradientItem<RGBA, P>>
+where
+    S: ToComputedValue<ComputedValue = generic::GradientItem<RGBA, P>>,
+    P: ColorStopPosition + PartialEq,
+{
+    let mut items = items
+        .iter()
+        .map(|item| item.to_computed_value(context))
+        .collect::<Vec<_>>();
+    fixup_color_stop_positions(&mut items);
+    collapse_duplicate_color_stops(&mut items);
+    items
+}
+
+/// Removes color stops that are identical to the color stop right before
+/// them, which don't change how the gradient is painted.
+///
+/// Stops without a position are never collapsed, since their position depends
+/// on the stops around them, and at least two color stops are always kept.
+fn collapse_duplicate_color_stops<P>(items: &mut Vec<generic::GradientItem<RGBA, P>>)
+where
+    P: PartialEq,
+{
+    let mut color_stops = items
+        .iter()
+        .filter(|item| match **item {
+            generic::GradientItem::ColorStop(..) => true,
+            generic::GradientItem::InterpolationHint(..) => false,
+        })
+        .count();
+    let mut index = 1;
+    while index < items.len() && color_stops > 2 {
+        let duplicate = match (&items[index - 1], &items[index]) {
+            (
+                &generic::GradientItem::ColorStop(ref previous),
+                &generic::GradientItem::ColorStop(ref stop),
+            ) => stop.position.is_some() && previous == stop,
+            _ => false,
+        };
+        if duplicate {
+            items.remove(index);
+            color_stops -= 1;
+        } else {
+            index += 1;
+        }
+    }
+}
+
+/// Makes the positions of color stops and interpolation hints monotonically
+/// non-decreasing.
+///
+/// Positions are only clamped to previous positions they're comparable with.
+/// Positions that aren't comparable even with themselves, like `calc()`
+/// lengths, are left untouched and don't constrain later positions.
+///
+/// <https://drafts.csswg.org/css-images-4/#color-stop-fixup>
+fn fixup_color_stop_positions<P>(items: &mut [generic::GradientItem<RGBA, P>])
+where
+    P: ColorStopPosition,
+{
+    // The largest position so far of each kind.
+    let mut maxima: Vec<P> = vec![];
+    for item in items.iter_mut() {
+        let position = match *item {
+            generic::GradientItem::ColorStop(generic::ColorStop {
+                position: Some(ref mut position),
+                ..
+            }) |
+            generic::GradientItem::InterpolationHint(ref mut position) => position,
+            generic::GradientItem::ColorStop(..) => continue,
+        };
+        let max = maxima.iter().find(|max| position.is_comparable(max)).cloned();
+        if let Some(max) = max {
+            if position.is_less_than(&max) {
+                *position = max;
+            }
+        }
+        if position.is_comparable(&*position) {
+            maxima.retain(|max| !position.is_comparable(max));
+            maxima.push(position.clone());
+        }
+    }
+}
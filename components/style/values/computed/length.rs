@@ -4,11 +4,13 @@
 
 //! `<length>` computed values, and related ones.
 
-use app_units::Au;
+use app_units::{Au, MAX_AU, MIN_AU};
+use euclid::Size2D;
 use logical_geometry::WritingMode;
 use ordered_float::NotNan;
 use properties::LonghandId;
 use std::fmt::{self, Write};
+use std::hash::{Hash, Hasher};
 use std::ops::{Add, Neg};
 use style_traits::{CssWriter, ToCss};
 use style_traits::values::specified::AllowedNumericType;
@@ -17,7 +19,9 @@ use values::{specified, Auto, CSSFloat, Either, Normal};
 use values::animated::{Animate, Procedure, ToAnimatedValue, ToAnimatedZero};
 use values::distance::{ComputeSquaredDistance, SquaredDistance};
 use values::generics::NonNegative;
-use values::specified::length::{AbsoluteLength, FontBaseSize, FontRelativeLength};
+use values::generics::length::Size as GenericSize;
+use values::specified::length::{AbsoluteLength, FontBaseSize, FontRelativeLength, AU_PER_PX};
+use values::specified::calc::{MinMaxOp, MAX_MIN_MAX_PERCENTAGES};
 use values::specified::length::ViewportPercentageLength;
 
 pub use super::image::Image;
@@ -57,6 +61,12 @@ impl ToComputedValue for specified::Length {
         match *self {
             specified::Length::NoCalc(l) => l.to_computed_value(context),
             specified::Length::Calc(ref calc) => calc.to_computed_value(context).length(),
+            specified::Length::MinMax(ref min_max) => {
+                min_max.to_computed_value(context).length()
+            },
+            specified::Length::Stepped(ref stepped) => {
+                stepped.to_computed_value(context).length()
+            },
         }
     }
 
@@ -67,17 +77,109 @@ impl ToComputedValue for specified::Length {
 }
 
 #[allow(missing_docs)]
-#[derive(Clone, Copy, Debug, MallocSizeOf, PartialEq, ToAnimatedZero)]
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
+#[derive(Clone, Copy, Debug, MallocSizeOf, PartialEq)]
 pub struct CalcLengthOrPercentage {
-    #[animation(constant)]
     pub clamping_mode: AllowedNumericType,
     length: Length,
     pub percentage: Option<Percentage>,
+    /// A `min()`, `max()` or `clamp()` function whose arguments can only be
+    /// compared once percentages are resolved.
+    ///
+    /// If present, `length` and `percentage` are those of its first argument
+    /// with a percentage, for the callers that only look at those.
+    min_max: Option<DeferredMinMax>,
+}
+
+/// An argument of a `DeferredMinMax`.
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct DeferredMinMaxArg {
+    length: Length,
+    percentage: Option<Percentage>,
+}
+
+/// A `min()`, `max()` or `clamp()` function whose arguments have different
+/// percentages, so that it can only be resolved at used value time.
+///
+/// The arguments with the same percentage have already been compared, so there
+/// are at most `MAX_MIN_MAX_PERCENTAGES` left, which is also the number of
+/// arguments of `clamp()`.
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct DeferredMinMax {
+    op: MinMaxOp,
+    len: usize,
+    args: [DeferredMinMaxArg; MAX_MIN_MAX_PERCENTAGES],
+}
+
+malloc_size_of_is_0!(DeferredMinMax);
+
+impl DeferredMinMax {
+    fn new(op: MinMaxOp, args: &[CalcLengthOrPercentage]) -> Self {
+        debug_assert!(args.len() <= MAX_MIN_MAX_PERCENTAGES);
+        let unused = DeferredMinMaxArg {
+            length: Length::new(0.),
+            percentage: None,
+        };
+        let mut min_max = DeferredMinMax {
+            op,
+            len: args.len(),
+            args: [unused; MAX_MIN_MAX_PERCENTAGES],
+        };
+        for (slot, arg) in min_max.args.iter_mut().zip(args.iter()) {
+            *slot = DeferredMinMaxArg {
+                length: arg.length,
+                percentage: arg.percentage,
+            };
+        }
+        min_max
+    }
+
+    fn args(&self) -> &[DeferredMinMaxArg] {
+        &self.args[..self.len]
+    }
+
+    /// Resolves the percentages of the arguments with `resolve`, and returns
+    /// the pixel value this function evaluates to.
+    fn resolve<F>(&self, resolve: F) -> CSSFloat
+    where
+        F: Fn(Percentage) -> CSSFloat,
+    {
+        let values = self.args()
+            .iter()
+            .map(|arg| arg.length.px() + arg.percentage.map_or(0., |p| resolve(p)));
+        self.op.select(values, |px| *px)
+    }
+}
+
+impl ToCss for DeferredMinMax {
+    fn to_css<W>(&self, dest: &mut CssWriter<W>) -> fmt::Result
+    where
+        W: Write,
+    {
+        self.op.to_css(dest)?;
+        dest.write_str("(")?;
+        for (i, arg) in self.args().iter().enumerate() {
+            if i != 0 {
+                dest.write_str(", ")?;
+            }
+            match arg.percentage {
+                None => arg.length.to_css(dest)?,
+                Some(p) if arg.length.px() == 0. => p.to_css(dest)?,
+                Some(p) => sum_to_css(p, arg.length, dest)?,
+            }
+        }
+        dest.write_str(")")
+    }
 }
 
 impl ComputeSquaredDistance for CalcLengthOrPercentage {
     #[inline]
     fn compute_squared_distance(&self, other: &Self) -> Result<SquaredDistance, ()> {
+        if self.has_deferred_min_max() || other.has_deferred_min_max() {
+            return Err(());
+        }
         // FIXME(nox): This looks incorrect to me, to add a distance between lengths
         // with a distance between percentages.
         Ok(self.unclamped_length()
@@ -105,9 +207,62 @@ impl CalcLengthOrPercentage {
             clamping_mode,
             length,
             percentage,
+            min_max: None,
+        }
+    }
+
+    /// Returns the value of a `min()`, `max()` or `clamp()` function of
+    /// `args`.
+    ///
+    /// Only arguments with the same percentage can be compared here, the
+    /// comparison between the others is deferred to used value time.
+    fn from_min_max(op: MinMaxOp, args: Vec<CalcLengthOrPercentage>) -> Self {
+        let key = |arg: &CalcLengthOrPercentage| arg.unclamped_length().px();
+        let args = match op {
+            MinMaxOp::Min | MinMaxOp::Max => {
+                let mut selected: Vec<CalcLengthOrPercentage> = Vec::with_capacity(args.len());
+                for arg in args {
+                    let same_percentage =
+                        selected.iter().position(|s| s.percentage() == arg.percentage());
+                    match same_percentage {
+                        Some(i) => selected[i] = op.select(vec![selected[i], arg], key),
+                        None => selected.push(arg),
+                    }
+                }
+                selected
+            },
+            MinMaxOp::Clamp => {
+                if args.iter().all(|arg| arg.percentage() == args[0].percentage()) {
+                    return op.select(args, key);
+                }
+                args
+            },
+        };
+
+        if args.len() == 1 {
+            return args[0];
+        }
+
+        let first = args.iter()
+            .find(|arg| arg.percentage.is_some())
+            .cloned()
+            .unwrap_or(args[0]);
+        CalcLengthOrPercentage {
+            min_max: Some(DeferredMinMax::new(op, &args)),
+            ..first
         }
     }
 
+    /// Whether this is a `min()`, `max()` or `clamp()` function that can only
+    /// be resolved at used value time.
+    ///
+    /// Its `length` and `percentage` don't represent it, so it can't be
+    /// interpolated for example.
+    #[inline]
+    pub fn has_deferred_min_max(&self) -> bool {
+        self.min_max.is_some()
+    }
+
     /// Returns this `calc()` as a `<length>`.
     ///
     /// Panics in debug mode if a percentage is present in the expression.
@@ -145,15 +300,91 @@ impl CalcLengthOrPercentage {
     /// the height property), they apply whenever a calc() expression contains
     /// percentages.
     pub fn to_pixel_length(&self, container_len: Option<Au>) -> Option<Length> {
+        if let Some(ref min_max) = self.min_max {
+            return container_len.map(|len| {
+                let pixel = min_max.resolve(|p| resolve_percentage(len, p).to_f32_px());
+                Length::new(self.clamping_mode.clamp(pixel))
+            });
+        }
+
         match (container_len, self.percentage) {
             (Some(len), Some(percent)) => {
-                let pixel = self.length.px() + len.scale_by(percent.0).to_f32_px();
+                let pixel = self.length.px() + resolve_percentage(len, percent).to_f32_px();
                 Some(Length::new(self.clamping_mode.clamp(pixel)))
             },
             (_, None) => Some(self.length()),
             _ => None,
         }
     }
+
+    /// Resolves percentages against `basis` in CSS pixels rather than app
+    /// units, like `line-height` does with the font size at computed value
+    /// time.
+    pub fn resolve_against_length(&self, basis: Length) -> Length {
+        use std::f32;
+        let resolve = |p: Percentage| (p.0 * basis.px()).min(f32::MAX).max(f32::MIN);
+        let pixel = match self.min_max {
+            Some(ref min_max) => min_max.resolve(resolve),
+            None => self.length.px() + self.percentage.map_or(0., |p| resolve(p)),
+        };
+        Length::new(self.clamping_mode.clamp(pixel))
+    }
+}
+
+impl ToAnimatedZero for CalcLengthOrPercentage {
+    #[inline]
+    fn to_animated_zero(&self) -> Result<Self, ()> {
+        if self.has_deferred_min_max() {
+            return Err(());
+        }
+        Ok(CalcLengthOrPercentage::with_clamping_mode(
+            self.length.to_animated_zero()?,
+            self.percentage.to_animated_zero()?,
+            self.clamping_mode,
+        ))
+    }
+}
+
+/// Resolves `percentage` against `basis`.
+///
+/// The multiplication is done in double precision and the result saturates to
+/// the range of app units, like lengths do when converted to `Au`, so that
+/// huge containing blocks or percentages don't wrap around.
+#[inline]
+fn resolve_percentage(basis: Au, percentage: Percentage) -> Au {
+    let au = (basis.0 as f64 * percentage.0 as f64).round();
+    Au(au.min(MAX_AU.0 as f64).max(MIN_AU.0 as f64) as i32)
+}
+
+/// The dimension of the containing block that percentages in a
+/// length-percentage value resolve against.
+///
+/// A computed value doesn't know this, since it depends on the property (e.g.
+/// `width` and `padding-top` resolve against the width of the containing
+/// block, but `height` and `top` resolve against its height), so the
+/// `resolve_for` methods of the length-percentage types take it.
+///
+/// There's no font size basis: percentages in `font-size` and `line-height`
+/// are resolved against the font size at computed value time, so they never
+/// reach a computed length-percentage.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PercentageBasis {
+    /// The width of the containing block.
+    Width,
+    /// The height of the containing block.
+    Height,
+}
+
+impl PercentageBasis {
+    /// Returns the length percentages resolve against, given the size of the
+    /// containing block.
+    #[inline]
+    pub fn length(self, containing_block: Size2D<Au>) -> Au {
+        match self {
+            PercentageBasis::Width => containing_block.width,
+            PercentageBasis::Height => containing_block.height,
+        }
+    }
 }
 
 impl From<LengthOrPercentage> for CalcLengthOrPercentage {
@@ -199,7 +430,9 @@ impl ToCss for CalcLengthOrPercentage {
     where
         W: Write,
     {
-        use num_traits::Zero;
+        if let Some(ref min_max) = self.min_max {
+            return min_max.to_css(dest);
+        }
 
         let (length, percentage) = match (self.length, self.percentage) {
             (l, None) => return l.to_css(dest),
@@ -208,19 +441,28 @@ impl ToCss for CalcLengthOrPercentage {
         };
 
         dest.write_str("calc(")?;
-        percentage.to_css(dest)?;
-
-        dest.write_str(if length.px() < Zero::zero() {
-            " - "
-        } else {
-            " + "
-        })?;
-        length.abs().to_css(dest)?;
-
+        sum_to_css(percentage, length, dest)?;
         dest.write_str(")")
     }
 }
 
+/// Serializes the sum of `percentage` and `length`, as it appears in a math
+/// function.
+fn sum_to_css<W>(percentage: Percentage, length: Length, dest: &mut CssWriter<W>) -> fmt::Result
+where
+    W: Write,
+{
+    use num_traits::Zero;
+
+    percentage.to_css(dest)?;
+    dest.write_str(if length.px() < Zero::zero() {
+        " - "
+    } else {
+        " + "
+    })?;
+    length.abs().to_css(dest)
+}
+
 impl specified::CalcLengthOrPercentage {
     /// Compute the value, zooming any absolute units by the zoom function.
     fn to_computed_value_with_zoom<F>(
@@ -262,11 +504,11 @@ impl specified::CalcLengthOrPercentage {
             }
         }
 
-        CalcLengthOrPercentage {
-            clamping_mode: self.clamping_mode,
-            length: Length::new(length.min(f32::MAX).max(f32::MIN)),
-            percentage: self.percentage,
-        }
+        CalcLengthOrPercentage::with_clamping_mode(
+            Length::new(length.min(f32::MAX).max(f32::MIN)),
+            self.percentage,
+            self.clamping_mode,
+        )
     }
 
     /// Compute font-size or line-height taking into account text-zoom if necessary.
@@ -321,9 +563,119 @@ impl ToComputedValue for specified::CalcLengthOrPercentage {
     }
 }
 
+impl specified::MinMaxLengthOrPercentage {
+    /// Computes the arguments with `compute_arg`, and compares them as far as
+    /// possible without resolving percentages.
+    fn select<F>(&self, compute_arg: F) -> CalcLengthOrPercentage
+    where
+        F: Fn(&specified::CalcLengthOrPercentage) -> CalcLengthOrPercentage,
+    {
+        CalcLengthOrPercentage::from_min_max(self.op, self.args.iter().map(compute_arg).collect())
+    }
+
+    /// Compute font-size or line-height taking into account text-zoom if necessary.
+    pub fn to_computed_value_zoomed(
+        &self,
+        context: &Context,
+        base_size: FontBaseSize,
+    ) -> CalcLengthOrPercentage {
+        self.select(|arg| arg.to_computed_value_zoomed(context, base_size))
+    }
+
+    /// Compute the value into pixel length as CSSFloat without context,
+    /// so it returns Err(()) if there is any non-absolute unit.
+    pub fn to_computed_pixel_length_without_context(&self) -> Result<CSSFloat, ()> {
+        let mut pixels = Vec::with_capacity(self.args.len());
+        for arg in self.args.iter() {
+            pixels.push(arg.to_computed_pixel_length_without_context()?);
+        }
+        Ok(self.op.select(pixels, |px| *px))
+    }
+}
+
+impl ToComputedValue for specified::MinMaxLengthOrPercentage {
+    type ComputedValue = CalcLengthOrPercentage;
+
+    fn to_computed_value(&self, context: &Context) -> CalcLengthOrPercentage {
+        self.select(|arg| arg.to_computed_value(context))
+    }
+
+    #[inline]
+    fn from_computed_value(computed: &CalcLengthOrPercentage) -> Self {
+        let min_max = match computed.min_max {
+            Some(ref min_max) => min_max,
+            None => {
+                return specified::MinMaxLengthOrPercentage {
+                    op: MinMaxOp::Min,
+                    args: vec![ToComputedValue::from_computed_value(computed)].into_boxed_slice(),
+                }
+            },
+        };
+
+        let args = min_max.args().iter().map(|arg| {
+            // Leave out zero lengths next to a percentage, so that they
+            // serialize like the computed value does.
+            let absolute = match arg.percentage {
+                Some(..) if arg.length.px() == 0. => None,
+                _ => Some(AbsoluteLength::from_computed_value(&arg.length)),
+            };
+            specified::CalcLengthOrPercentage {
+                clamping_mode: computed.clamping_mode,
+                absolute,
+                percentage: arg.percentage,
+                ..Default::default()
+            }
+        });
+        specified::MinMaxLengthOrPercentage {
+            op: min_max.op,
+            args: args.collect::<Vec<_>>().into_boxed_slice(),
+        }
+    }
+}
+
+impl specified::SteppedLength {
+    /// Computes the arguments with `compute_arg`, and applies the function to
+    /// their lengths.
+    fn apply<F>(&self, compute_arg: F) -> CalcLengthOrPercentage
+    where
+        F: Fn(&specified::CalcLengthOrPercentage) -> CalcLengthOrPercentage,
+    {
+        let value = compute_arg(&self.value);
+        let step = compute_arg(&self.step);
+        let px = self.op.apply(value.unclamped_length().px(), step.unclamped_length().px());
+        CalcLengthOrPercentage::with_clamping_mode(Length::new(px), None, value.clamping_mode)
+    }
+
+    /// Computes this function against the given context.
+    ///
+    /// This isn't a `ToComputedValue` implementation since the computed
+    /// length can't be turned back into a stepped function.
+    pub fn to_computed_value(&self, context: &Context) -> CalcLengthOrPercentage {
+        self.apply(|arg| arg.to_computed_value(context))
+    }
+
+    /// Compute font-size or line-height taking into account text-zoom if necessary.
+    pub fn to_computed_value_zoomed(
+        &self,
+        context: &Context,
+        base_size: FontBaseSize,
+    ) -> CalcLengthOrPercentage {
+        self.apply(|arg| arg.to_computed_value_zoomed(context, base_size))
+    }
+
+    /// Compute the value into pixel length as CSSFloat without context,
+    /// so it returns Err(()) if there is any non-absolute unit.
+    pub fn to_computed_pixel_length_without_context(&self) -> Result<CSSFloat, ()> {
+        let value = self.value.to_computed_pixel_length_without_context()?;
+        let step = self.step.to_computed_pixel_length_without_context()?;
+        Ok(self.value.clamping_mode.clamp(self.op.apply(value, step)))
+    }
+}
+
 #[allow(missing_docs)]
 #[animate(fallback = "Self::animate_fallback")]
 #[css(derive_debug)]
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Animate, Clone, ComputeSquaredDistance, Copy, MallocSizeOf, PartialEq,
          ToAnimatedValue, ToAnimatedZero, ToCss)]
 #[distance(fallback = "Self::compute_squared_distance_fallback")]
@@ -375,6 +727,19 @@ impl LengthOrPercentage {
         LengthOrPercentage::Length(Length::new(1.))
     }
 
+    /// Returns a length value of `au` app units.
+    #[inline]
+    pub fn from_au(au: Au) -> Self {
+        LengthOrPercentage::Length(au.into())
+    }
+
+    /// Returns a percentage value, where `percentage` is normalized to the
+    /// `[0 .. 1]` range for `0%` to `100%`.
+    #[inline]
+    pub fn from_percentage(percentage: CSSFloat) -> Self {
+        LengthOrPercentage::Percentage(Percentage(percentage))
+    }
+
     /// Returns true if the computed value is absolute 0 or 0%.
     ///
     /// (Returns false for calc() values, even if ones that may resolve to zero.)
@@ -408,11 +773,18 @@ impl LengthOrPercentage {
         Au::from(self.to_pixel_length(containing_length))
     }
 
+    /// Resolves this value to a used length, with percentages resolved
+    /// against the `basis` dimension of the containing block.
+    #[inline]
+    pub fn resolve_for(&self, basis: PercentageBasis, containing_block: Size2D<Au>) -> Au {
+        self.to_used_value(basis.length(containing_block))
+    }
+
     /// Returns the used value as CSSPixelLength.
     pub fn to_pixel_length(&self, containing_length: Au) -> Length {
         match *self {
             LengthOrPercentage::Length(length) => length,
-            LengthOrPercentage::Percentage(p) => containing_length.scale_by(p.0).into(),
+            LengthOrPercentage::Percentage(p) => resolve_percentage(containing_length, p).into(),
             LengthOrPercentage::Calc(ref calc) => {
                 calc.to_pixel_length(Some(containing_length)).unwrap()
             },
@@ -432,6 +804,60 @@ impl LengthOrPercentage {
             _ => self,
         }
     }
+
+    /// Returns a specified value that computes to this one, for displaying
+    /// computed values next to specified ones.
+    ///
+    /// This is lossy: lengths come back as absolute pixel lengths, so the
+    /// original units (like `em`) can't be recovered.
+    #[inline]
+    pub fn to_specified(&self) -> specified::LengthOrPercentage {
+        ToComputedValue::from_computed_value(self)
+    }
+}
+
+/// Returns the bits of `value` to hash, so that `0.0` and `-0.0`, which
+/// compare equal, also hash equally.
+#[inline]
+fn float_hash_bits(value: CSSFloat) -> u32 {
+    if value == 0. {
+        0
+    } else {
+        value.to_bits()
+    }
+}
+
+// Computed lengths and percentages are compared as floats, so strictly
+// speaking this doesn't hold for NaN, which is never equal to itself. NaN
+// can't be produced by parsing though, so in practice this is fine to use for
+// hash map keys.
+impl Eq for LengthOrPercentage {}
+
+impl Hash for LengthOrPercentage {
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        match *self {
+            LengthOrPercentage::Length(l) => {
+                hasher.write_u8(0);
+                hasher.write_u32(float_hash_bits(l.px()));
+            },
+            LengthOrPercentage::Percentage(p) => {
+                hasher.write_u8(1);
+                hasher.write_u32(float_hash_bits(p.0));
+            },
+            LengthOrPercentage::Calc(ref calc) => {
+                hasher.write_u8(2);
+                hasher.write_u32(float_hash_bits(calc.unclamped_length().px()));
+                calc.percentage.map(|p| float_hash_bits(p.0)).hash(hasher);
+                if let Some(ref min_max) = calc.min_max {
+                    min_max.op.hash(hasher);
+                    for arg in min_max.args() {
+                        hasher.write_u32(float_hash_bits(arg.length.px()));
+                        arg.percentage.map(|p| float_hash_bits(p.0)).hash(hasher);
+                    }
+                }
+            },
+        }
+    }
 }
 
 impl ToComputedValue for specified::LengthOrPercentage {
@@ -448,6 +874,12 @@ impl ToComputedValue for specified::LengthOrPercentage {
             specified::LengthOrPercentage::Calc(ref calc) => {
                 LengthOrPercentage::Calc((**calc).to_computed_value(context))
             },
+            specified::LengthOrPercentage::MinMax(ref min_max) => {
+                LengthOrPercentage::Calc((**min_max).to_computed_value(context))
+            },
+            specified::LengthOrPercentage::Stepped(ref stepped) => {
+                LengthOrPercentage::Calc(stepped.to_computed_value(context))
+            },
         }
     }
 
@@ -459,6 +891,11 @@ impl ToComputedValue for specified::LengthOrPercentage {
             LengthOrPercentage::Percentage(value) => {
                 specified::LengthOrPercentage::Percentage(value)
             },
+            LengthOrPercentage::Calc(ref calc) if calc.has_deferred_min_max() => {
+                specified::LengthOrPercentage::MinMax(Box::new(
+                    ToComputedValue::from_computed_value(calc),
+                ))
+            },
             LengthOrPercentage::Calc(ref calc) => specified::LengthOrPercentage::Calc(Box::new(
                 ToComputedValue::from_computed_value(calc),
             )),
@@ -469,6 +906,7 @@ impl ToComputedValue for specified::LengthOrPercentage {
 #[allow(missing_docs)]
 #[animate(fallback = "Self::animate_fallback")]
 #[css(derive_debug)]
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Animate, Clone, ComputeSquaredDistance, Copy, MallocSizeOf, PartialEq, ToCss)]
 #[distance(fallback = "Self::compute_squared_distance_fallback")]
 pub enum LengthOrPercentageOrAuto {
@@ -525,6 +963,19 @@ impl ToAnimatedValue for NonNegativeLengthOrPercentageOrAuto {
 }
 
 impl LengthOrPercentageOrAuto {
+    /// Returns a length value of `au` app units.
+    #[inline]
+    pub fn from_au(au: Au) -> Self {
+        LengthOrPercentageOrAuto::Length(au.into())
+    }
+
+    /// Returns a percentage value, where `percentage` is normalized to the
+    /// `[0 .. 1]` range for `0%` to `100%`.
+    #[inline]
+    pub fn from_percentage(percentage: CSSFloat) -> Self {
+        LengthOrPercentageOrAuto::Percentage(Percentage(percentage))
+    }
+
     /// Returns true if the computed value is absolute 0 or 0%.
     ///
     /// (Returns false for calc() values, even if ones that may resolve to zero.)
@@ -538,6 +989,28 @@ impl LengthOrPercentageOrAuto {
         }
     }
 
+    /// Returns the used value, or `None` for `auto`.
+    pub fn to_used_value(&self, containing_length: Au) -> Option<Au> {
+        match *self {
+            LengthOrPercentageOrAuto::Auto => None,
+            LengthOrPercentageOrAuto::Length(length) => Some(Au::from(length)),
+            LengthOrPercentageOrAuto::Percentage(percent) => {
+                Some(resolve_percentage(containing_length, percent))
+            },
+            LengthOrPercentageOrAuto::Calc(ref calc) => calc.to_used_value(Some(containing_length)),
+        }
+    }
+
+    /// Like `LengthOrPercentage::resolve_for`, but returns `None` for `auto`.
+    #[inline]
+    pub fn resolve_for(
+        &self,
+        basis: PercentageBasis,
+        containing_block: Size2D<Au>,
+    ) -> Option<Au> {
+        self.to_used_value(basis.length(containing_block))
+    }
+
     fn clamp_to_non_negative(self) -> Self {
         use self::LengthOrPercentageOrAuto::*;
         match self {
@@ -546,6 +1019,16 @@ impl LengthOrPercentageOrAuto {
             _ => self,
         }
     }
+
+    /// Returns a specified value that computes to this one, for displaying
+    /// computed values next to specified ones.
+    ///
+    /// This is lossy: lengths come back as absolute pixel lengths, so the
+    /// original units (like `em`) can't be recovered.
+    #[inline]
+    pub fn to_specified(&self) -> specified::LengthOrPercentageOrAuto {
+        ToComputedValue::from_computed_value(self)
+    }
 }
 
 impl ToComputedValue for specified::LengthOrPercentageOrAuto {
@@ -564,6 +1047,9 @@ impl ToComputedValue for specified::LengthOrPercentageOrAuto {
             specified::LengthOrPercentageOrAuto::Calc(ref calc) => {
                 LengthOrPercentageOrAuto::Calc((**calc).to_computed_value(context))
             },
+            specified::LengthOrPercentageOrAuto::MinMax(ref min_max) => {
+                LengthOrPercentageOrAuto::Calc((**min_max).to_computed_value(context))
+            },
         }
     }
 
@@ -577,6 +1063,11 @@ impl ToComputedValue for specified::LengthOrPercentageOrAuto {
             LengthOrPercentageOrAuto::Percentage(value) => {
                 specified::LengthOrPercentageOrAuto::Percentage(value)
             },
+            LengthOrPercentageOrAuto::Calc(calc) if calc.has_deferred_min_max() => {
+                specified::LengthOrPercentageOrAuto::MinMax(Box::new(
+                    ToComputedValue::from_computed_value(&calc),
+                ))
+            },
             LengthOrPercentageOrAuto::Calc(calc) => specified::LengthOrPercentageOrAuto::Calc(
                 Box::new(ToComputedValue::from_computed_value(&calc)),
             ),
@@ -586,7 +1077,7 @@ impl ToComputedValue for specified::LengthOrPercentageOrAuto {
 
 #[allow(missing_docs)]
 #[animate(fallback = "Self::animate_fallback")]
-#[cfg_attr(feature = "servo", derive(MallocSizeOf))]
+#[cfg_attr(feature = "servo", derive(Deserialize, MallocSizeOf, Serialize))]
 #[css(derive_debug)]
 #[derive(Animate, Clone, ComputeSquaredDistance, Copy, PartialEq, ToCss)]
 #[distance(fallback = "Self::compute_squared_distance_fallback")]
@@ -618,17 +1109,50 @@ impl LengthOrPercentageOrNone {
 }
 
 impl LengthOrPercentageOrNone {
+    /// Returns a length value of `au` app units.
+    #[inline]
+    pub fn from_au(au: Au) -> Self {
+        LengthOrPercentageOrNone::Length(au.into())
+    }
+
+    /// Returns a percentage value, where `percentage` is normalized to the
+    /// `[0 .. 1]` range for `0%` to `100%`.
+    #[inline]
+    pub fn from_percentage(percentage: CSSFloat) -> Self {
+        LengthOrPercentageOrNone::Percentage(Percentage(percentage))
+    }
+
     /// Returns the used value.
     pub fn to_used_value(&self, containing_length: Au) -> Option<Au> {
         match *self {
             LengthOrPercentageOrNone::None => None,
             LengthOrPercentageOrNone::Length(length) => Some(Au::from(length)),
             LengthOrPercentageOrNone::Percentage(percent) => {
-                Some(containing_length.scale_by(percent.0))
+                Some(resolve_percentage(containing_length, percent))
             },
             LengthOrPercentageOrNone::Calc(ref calc) => calc.to_used_value(Some(containing_length)),
         }
     }
+
+    /// Like `LengthOrPercentage::resolve_for`, but returns `None` for `none`.
+    #[inline]
+    pub fn resolve_for(
+        &self,
+        basis: PercentageBasis,
+        containing_block: Size2D<Au>,
+    ) -> Option<Au> {
+        self.to_used_value(basis.length(containing_block))
+    }
+
+    /// Returns a specified value that computes to this one, for displaying
+    /// computed values next to specified ones.
+    ///
+    /// This is lossy: lengths come back as absolute pixel lengths, so the
+    /// original units (like `em`) can't be recovered.
+    #[inline]
+    pub fn to_specified(&self) -> specified::LengthOrPercentageOrNone {
+        ToComputedValue::from_computed_value(self)
+    }
 }
 
 impl ToComputedValue for specified::LengthOrPercentageOrNone {
@@ -646,6 +1170,9 @@ impl ToComputedValue for specified::LengthOrPercentageOrNone {
             specified::LengthOrPercentageOrNone::Calc(ref calc) => {
                 LengthOrPercentageOrNone::Calc((**calc).to_computed_value(context))
             },
+            specified::LengthOrPercentageOrNone::MinMax(ref min_max) => {
+                LengthOrPercentageOrNone::Calc((**min_max).to_computed_value(context))
+            },
             specified::LengthOrPercentageOrNone::None => LengthOrPercentageOrNone::None,
         }
     }
@@ -660,6 +1187,11 @@ impl ToComputedValue for specified::LengthOrPercentageOrNone {
             LengthOrPercentageOrNone::Percentage(value) => {
                 specified::LengthOrPercentageOrNone::Percentage(value)
             },
+            LengthOrPercentageOrNone::Calc(calc) if calc.has_deferred_min_max() => {
+                specified::LengthOrPercentageOrNone::MinMax(Box::new(
+                    ToComputedValue::from_computed_value(&calc),
+                ))
+            },
             LengthOrPercentageOrNone::Calc(calc) => specified::LengthOrPercentageOrNone::Calc(
                 Box::new(ToComputedValue::from_computed_value(&calc)),
             ),
@@ -723,6 +1255,12 @@ impl NonNegativeLengthOrPercentage {
     pub fn to_used_value(&self, containing_length: Au) -> Au {
         self.0.to_used_value(containing_length)
     }
+
+    /// See `LengthOrPercentage::resolve_for`.
+    #[inline]
+    pub fn resolve_for(&self, basis: PercentageBasis, containing_block: Size2D<Au>) -> Au {
+        self.0.resolve_for(basis, containing_block)
+    }
 }
 
 /// The computed `<length>` value.
@@ -789,7 +1327,11 @@ impl Neg for CSSPixelLength {
 impl From<CSSPixelLength> for Au {
     #[inline]
     fn from(len: CSSPixelLength) -> Self {
-        Au::from_f32_px(len.0)
+        // Saturate to the range of app units, rather than overflowing for
+        // huge values like `100000000px`, and round to the nearest app unit
+        // rather than truncating towards zero.
+        let px = len.0.min(MAX_AU.to_f32_px()).max(MIN_AU.to_f32_px());
+        Au((px * AU_PER_PX).round() as i32)
     }
 }
 
@@ -1022,6 +1564,17 @@ impl ToComputedValue for specified::MozLength {
     }
 }
 
+/// A computed value for the `width` or `height` properties.
+pub type Size = GenericSize<LengthOrPercentageOrAuto, LengthOrPercentage>;
+
+impl Size {
+    /// Returns the `auto` value.
+    #[inline]
+    pub fn auto() -> Self {
+        GenericSize::LengthOrPercentageOrAuto(LengthOrPercentageOrAuto::Auto)
+    }
+}
+
 /// A value suitable for a `max-width` or `max-height` property.
 /// See values/specified/length.rs for more details.
 #[allow(missing_docs)]
@@ -43,10 +43,10 @@ pub use self::font::{FontSize, FontSizeAdjust, FontStretch, FontSynthesis, FontV
 pub use self::font::{FontFamily, FontLanguageOverride, FontStyle, FontVariantEastAsian, FontVariationSettings};
 pub use self::font::{FontFeatureSettings, FontVariantLigatures, FontVariantNumeric};
 pub use self::font::{MozScriptLevel, MozScriptMinSize, MozScriptSizeMultiplier, XLang, XTextZoom};
-pub use self::box_::{AnimationIterationCount, AnimationName, Contain, Display};
-pub use self::box_::{OverflowClipBox, OverscrollBehavior, Perspective};
+pub use self::box_::{AnimationIterationCount, AnimationName, AspectRatio, Contain, Display};
+pub use self::box_::{OverflowClipBox, OverscrollBehavior, Perspective, Ratio};
 pub use self::box_::{ScrollSnapType, TouchAction, VerticalAlign, WillChange};
-pub use self::color::{Color, ColorPropertyValue, RGBAColor};
+pub use self::color::{Color, ColorInterpolationMethod, ColorPropertyValue, RGBAColor};
 pub use self::column::ColumnCount;
 pub use self::counters::{Content, ContentItem, CounterIncrement, CounterReset};
 pub use self::effects::{BoxShadow, Filter, SimpleShadow};
@@ -61,8 +61,9 @@ pub use super::{Auto, Either, None_};
 pub use super::specified::{BorderStyle, TextDecorationLine};
 pub use self::length::{CalcLengthOrPercentage, Length, LengthOrNumber, LengthOrPercentage};
 pub use self::length::{LengthOrPercentageOrAuto, LengthOrPercentageOrNone, MaxLength, MozLength};
-pub use self::length::{CSSPixelLength, ExtremumLength, NonNegativeLength};
+pub use self::length::{CSSPixelLength, ExtremumLength, NonNegativeLength, Size};
 pub use self::length::{NonNegativeLengthOrPercentage, NonNegativeLengthOrPercentageOrAuto};
+pub use self::length::PercentageBasis;
 pub use self::list::Quotes;
 #[cfg(feature = "gecko")]
 pub use self::list::ListStyleType;
@@ -193,6 +194,29 @@ impl<'a> Context<'a> {
         f(&context)
     }
 
+    /// Creates a context with default values for tests, and executes `f` with
+    /// it.
+    ///
+    /// The context computes against an 800x600 screen device with a device
+    /// pixel ratio of 1, for a non-root element without quirks, and with the
+    /// initial style: a 16px font size, black color, no borders, and neither
+    /// positioned nor floated. Use `ContextBuilder` to customize any of these.
+    #[cfg(feature = "servo")]
+    pub fn for_testing<F, R>(f: F) -> R
+    where
+        F: FnOnce(&Context) -> R,
+    {
+        use euclid::{TypedScale, TypedSize2D};
+        use media_queries::MediaType;
+
+        let device = Device::new(
+            MediaType::screen(),
+            TypedSize2D::new(800., 600.),
+            TypedScale::new(1.),
+        );
+        ContextBuilder::new(&device).build(f)
+    }
+
     /// Whether the current element is the root element.
     pub fn is_root_element(&self) -> bool {
         self.is_root_element
@@ -240,6 +264,122 @@ impl<'a> Context<'a> {
     }
 }
 
+/// A builder for a `Context` outside of the cascade, e.g. for tests or
+/// embedders that want to compute standalone values.
+///
+/// By default the context is for a non-root element with the initial style of
+/// the device (so a 16px font size, black color, static position, etc), no
+/// quirks, and the font metrics provider of the current product.
+pub struct ContextBuilder<'a> {
+    device: &'a Device,
+    is_root_element: bool,
+    in_media_query: bool,
+    quirks_mode: QuirksMode,
+    for_smil_animation: bool,
+    font_metrics_provider: Option<&'a FontMetricsProvider>,
+    font_size: Option<Length>,
+    color: Option<ColorPropertyValue>,
+}
+
+impl<'a> ContextBuilder<'a> {
+    /// Creates a new builder for a context computing against `device`.
+    pub fn new(device: &'a Device) -> Self {
+        ContextBuilder {
+            device,
+            is_root_element: false,
+            in_media_query: false,
+            quirks_mode: QuirksMode::NoQuirks,
+            for_smil_animation: false,
+            font_metrics_provider: None,
+            font_size: None,
+            color: None,
+        }
+    }
+
+    /// Sets whether the element is the root element.
+    pub fn with_root_element(mut self, is_root_element: bool) -> Self {
+        self.is_root_element = is_root_element;
+        self
+    }
+
+    /// Sets whether values are being computed for a media query.
+    pub fn with_media_query(mut self, in_media_query: bool) -> Self {
+        self.in_media_query = in_media_query;
+        self
+    }
+
+    /// Sets the quirks mode.
+    pub fn with_quirks_mode(mut self, quirks_mode: QuirksMode) -> Self {
+        self.quirks_mode = quirks_mode;
+        self
+    }
+
+    /// Sets whether values are being computed for a SMIL animation.
+    pub fn with_smil_animation(mut self, for_smil_animation: bool) -> Self {
+        self.for_smil_animation = for_smil_animation;
+        self
+    }
+
+    /// Sets the font metrics provider used to resolve font-relative units.
+    pub fn with_font_metrics_provider(mut self, provider: &'a FontMetricsProvider) -> Self {
+        self.font_metrics_provider = Some(provider);
+        self
+    }
+
+    /// Sets the computed font size of the element.
+    pub fn with_font_size(mut self, font_size: Length) -> Self {
+        self.font_size = Some(font_size);
+        self
+    }
+
+    /// Sets the computed color of the element.
+    pub fn with_color(mut self, color: ColorPropertyValue) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Builds the context, and executes `f` with it.
+    pub fn build<F, R>(self, f: F) -> R
+    where
+        F: FnOnce(&Context) -> R,
+    {
+        let mut conditions = RuleCacheConditions::default();
+        let default_provider;
+        let provider: &FontMetricsProvider = match self.font_metrics_provider {
+            Some(provider) => provider,
+            None => {
+                default_provider = get_metrics_provider_for_product();
+                &default_provider
+            },
+        };
+
+        let mut builder = StyleBuilder::for_inheritance(self.device, None, None);
+        if let Some(size) = self.font_size {
+            builder.mutate_font().set_font_size(FontSize {
+                size: NonNegative(size),
+                keyword_info: None,
+            });
+        }
+        if let Some(color) = self.color {
+            builder.mutate_color().set_color(color);
+        }
+
+        let context = Context {
+            is_root_element: self.is_root_element,
+            builder,
+            font_metrics_provider: provider,
+            cached_system_font: None,
+            in_media_query: self.in_media_query,
+            quirks_mode: self.quirks_mode,
+            for_smil_animation: self.for_smil_animation,
+            for_non_inherited_property: None,
+            rule_cache_conditions: RefCell::new(&mut conditions),
+        };
+
+        f(&context)
+    }
+}
+
 /// An iterator over a slice of computed values
 #[derive(Clone)]
 pub struct ComputedVecIter<'a, 'cx, 'cx_a: 'cx, S: ToComputedValue + 'a> {
@@ -514,6 +654,7 @@ impl From<GreaterThanOrEqualToOneNumber> for CSSFloat {
 }
 
 #[allow(missing_docs)]
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, ComputeSquaredDistance, Copy, Debug, MallocSizeOf, PartialEq, ToCss)]
 pub enum NumberOrPercentage {
     Percentage(Percentage),
@@ -549,6 +690,37 @@ impl ToComputedValue for specified::NumberOrPercentage {
     }
 }
 
+impl NumberOrPercentage {
+    /// Returns this value as a fraction, so that both `0.5` and `50%` are
+    /// `0.5`.
+    #[inline]
+    pub fn to_fraction(&self) -> CSSFloat {
+        match *self {
+            NumberOrPercentage::Percentage(percentage) => percentage.0,
+            NumberOrPercentage::Number(number) => number,
+        }
+    }
+
+    /// Returns this value as a fraction clamped to the `[0, 1]` range, as used
+    /// by things like color-mix() weights or cross-fade() percentages.
+    #[inline]
+    pub fn clamped_fraction(&self) -> CSSFloat {
+        self.to_fraction().max(0.).min(1.)
+    }
+
+    /// Returns this value with negative values clamped to zero, keeping the
+    /// kind of value it is.
+    #[inline]
+    pub fn clamp_to_non_negative(self) -> Self {
+        match self {
+            NumberOrPercentage::Percentage(percentage) => {
+                NumberOrPercentage::Percentage(Percentage(percentage.0.max(0.)))
+            },
+            NumberOrPercentage::Number(number) => NumberOrPercentage::Number(number.max(0.)),
+        }
+    }
+}
+
 /// A type used for opacity.
 pub type Opacity = CSSFloat;
 
@@ -13,6 +13,8 @@ use values::computed::{Context, ToComputedValue};
 use values::specified;
 
 /// A computed `<resolution>`.
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
+#[derive(Clone, Copy, Debug, MallocSizeOf, PartialEq)]
 pub struct Resolution(CSSFloat);
 
 impl Resolution {
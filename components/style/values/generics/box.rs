@@ -4,6 +4,8 @@
 
 //! Generic types for box properties.
 
+use std::fmt::{self, Write};
+use style_traits::{CssWriter, ToCss};
 use values::animated::ToAnimatedZero;
 
 /// A generic value for the `vertical-align` property.
@@ -75,3 +77,63 @@ impl<L> Perspective<L> {
         Perspective::None
     }
 }
+
+/// A `<ratio>` value, i.e. `<number> / <number>`.
+///
+/// <https://drafts.csswg.org/css-values-4/#ratios>
+#[derive(Clone, Copy, Debug, MallocSizeOf, PartialEq, SpecifiedValueInfo,
+         ToComputedValue)]
+pub struct Ratio<Number>(pub Number, pub Number);
+
+impl<N: ToCss> ToCss for Ratio<N> {
+    fn to_css<W>(&self, dest: &mut CssWriter<W>) -> fmt::Result
+    where
+        W: Write,
+    {
+        self.0.to_css(dest)?;
+        dest.write_str(" / ")?;
+        self.1.to_css(dest)
+    }
+}
+
+/// A generic value for the `aspect-ratio` property.
+///
+/// <https://drafts.csswg.org/css-sizing-4/#aspect-ratio>
+#[derive(Clone, Copy, Debug, MallocSizeOf, PartialEq, SpecifiedValueInfo,
+         ToComputedValue)]
+#[value_info(other_values = "auto")]
+pub struct AspectRatio<Number> {
+    /// Whether the `auto` keyword was specified.
+    pub auto: bool,
+    /// The preferred ratio, if any. Either this or `auto` is always present.
+    pub ratio: Option<Ratio<Number>>,
+}
+
+impl<N> AspectRatio<N> {
+    /// Returns `auto`.
+    #[inline]
+    pub fn auto() -> Self {
+        AspectRatio {
+            auto: true,
+            ratio: None,
+        }
+    }
+}
+
+impl<N: ToCss> ToCss for AspectRatio<N> {
+    fn to_css<W>(&self, dest: &mut CssWriter<W>) -> fmt::Result
+    where
+        W: Write,
+    {
+        if self.auto {
+            dest.write_str("auto")?;
+        }
+        if let Some(ref ratio) = self.ratio {
+            if self.auto {
+                dest.write_str(" ")?;
+            }
+            ratio.to_css(dest)?;
+        }
+        Ok(())
+    }
+}
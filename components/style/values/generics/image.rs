@@ -12,22 +12,45 @@ use servo_arc::Arc;
 use std::fmt::{self, Write};
 use style_traits::{CssWriter, ToCss};
 use values::serialize_atom_identifier;
+use values::specified::Attr;
+use values::specified::color::ColorInterpolationMethod;
 
 /// An [image].
 ///
 /// [image]: https://drafts.csswg.org/css-images/#image-values
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, MallocSizeOf, PartialEq, SpecifiedValueInfo, ToComputedValue)]
-pub enum Image<Gradient, MozImageRect, ImageUrl> {
+pub enum Image<Gradient, ConicGradient, MozImageRect, ImageUrl, Percentage, Resolution, Color> {
     /// A `<url()>` image.
     Url(ImageUrl),
     /// A `<gradient>` image.  Gradients are rather large, and not nearly as
     /// common as urls, so we box them here to keep the size of this enum sane.
     Gradient(Box<Gradient>),
+    /// A `<conic-gradient()>` image.
+    ConicGradient(Box<ConicGradient>),
     /// A `-moz-image-rect` image.  Also fairly large and rare.
     Rect(Box<MozImageRect>),
+    /// A `cross-fade()` image.
+    #[css(function = "cross-fade")]
+    CrossFade(Box<CrossFade<
+        Image<Gradient, ConicGradient, MozImageRect, ImageUrl, Percentage, Resolution, Color>,
+        Percentage,
+    >>),
+    /// An `image-set()` image.
+    #[css(function = "image-set")]
+    ImageSet(Box<ImageSet<
+        Image<Gradient, ConicGradient, MozImageRect, ImageUrl, Percentage, Resolution, Color>,
+        Resolution,
+    >>),
     /// A `-moz-element(# <element-id>)`
     #[css(function = "-moz-element")]
     Element(Atom),
+    /// An `image()` image, with an optional solid-color fallback.
+    #[css(function = "image")]
+    Image(Box<ImageFunction<ImageUrl, Color>>),
+    /// An `attr(<attr-name> url)` image, whose url is taken from an attribute
+    /// of the element. It is left unresolved at computed-value time.
+    Attr(Attr),
     /// A paint worklet image.
     /// <https://drafts.css-houdini.org/css-paint-api/>
     #[cfg(feature = "servo")]
@@ -36,7 +59,8 @@ pub enum Image<Gradient, MozImageRect, ImageUrl> {
 
 /// A CSS gradient.
 /// <https://drafts.csswg.org/css-images/#gradients>
-#[derive(Clone, Debug, MallocSizeOf, PartialEq, ToComputedValue)]
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
+#[derive(Clone, Debug, MallocSizeOf, PartialEq)]
 pub struct Gradient<LineDirection, Length, LengthOrPercentage, Position, Color, Angle> {
     /// Gradients can be linear or radial.
     pub kind: GradientKind<LineDirection, Length, LengthOrPercentage, Position, Angle>,
@@ -46,10 +70,28 @@ pub struct Gradient<LineDirection, Length, LengthOrPercentage, Position, Color,
     pub repeating: bool,
     /// Compatibility mode.
     pub compat_mode: CompatMode,
+    /// The color space the gradient is interpolated in, if specified.
+    pub color_interpolation: Option<ColorInterpolationMethod>,
+}
+
+/// A CSS conic gradient.
+/// <https://drafts.csswg.org/css-images-4/#conic-gradients>
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
+#[derive(Clone, Debug, MallocSizeOf, PartialEq)]
+pub struct ConicGradient<Angle, Position, Color, AngleOrPercentage> {
+    /// The angle the gradient starts at, if specified.
+    pub angle: Option<Angle>,
+    /// The center of the gradient, if specified.
+    pub position: Option<Position>,
+    /// The color stops and interpolation hints.
+    pub items: Vec<GradientItem<Color, AngleOrPercentage>>,
+    /// True if this is a repeating gradient.
+    pub repeating: bool,
 }
 
-#[derive(Clone, Copy, Debug, MallocSizeOf, PartialEq, ToComputedValue)]
 /// Whether we used the modern notation or the compatibility `-webkit`, `-moz` prefixes.
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
+#[derive(Clone, Copy, Debug, MallocSizeOf, PartialEq, ToComputedValue)]
 pub enum CompatMode {
     /// Modern syntax.
     Modern,
@@ -60,6 +102,7 @@ pub enum CompatMode {
 }
 
 /// A gradient kind.
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Copy, Debug, MallocSizeOf, PartialEq, ToComputedValue)]
 pub enum GradientKind<LineDirection, Length, LengthOrPercentage, Position, Angle> {
     /// A linear gradient.
@@ -73,6 +116,7 @@ pub enum GradientKind<LineDirection, Length, LengthOrPercentage, Position, Angle
 }
 
 /// A radial gradient's ending shape.
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Copy, Debug, MallocSizeOf, PartialEq, ToComputedValue, ToCss)]
 pub enum EndingShape<Length, LengthOrPercentage> {
     /// A circular gradient.
@@ -82,6 +126,7 @@ pub enum EndingShape<Length, LengthOrPercentage> {
 }
 
 /// A circle shape.
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Copy, Debug, MallocSizeOf, PartialEq, ToComputedValue)]
 pub enum Circle<Length> {
     /// A circle radius.
@@ -91,6 +136,7 @@ pub enum Circle<Length> {
 }
 
 /// An ellipse shape.
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Copy, Debug, MallocSizeOf, PartialEq, ToComputedValue, ToCss)]
 pub enum Ellipse<LengthOrPercentage> {
     /// An ellipse pair of radii.
@@ -114,6 +160,7 @@ pub enum ShapeExtent {
 
 /// A gradient item.
 /// <https://drafts.csswg.org/css-images-4/#color-stop-syntax>
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Copy, Debug, MallocSizeOf, PartialEq, ToComputedValue, ToCss)]
 pub enum GradientItem<Color, LengthOrPercentage> {
     /// A color stop.
@@ -124,6 +171,7 @@ pub enum GradientItem<Color, LengthOrPercentage> {
 
 /// A color stop.
 /// <https://drafts.csswg.org/css-images/#typedef-color-stop-list>
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Copy, Debug, MallocSizeOf, PartialEq, ToComputedValue, ToCss)]
 pub struct ColorStop<Color, LengthOrPercentage> {
     /// The color of this stop.
@@ -132,16 +180,86 @@ pub struct ColorStop<Color, LengthOrPercentage> {
     pub position: Option<LengthOrPercentage>,
 }
 
+/// A `cross-fade()` image, blending several images together.
+/// <https://drafts.csswg.org/css-images-4/#cross-fade-function>
+#[css(comma, function)]
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
+#[derive(Clone, Debug, MallocSizeOf, PartialEq, ToComputedValue, ToCss)]
+pub struct CrossFade<Image, Percentage> {
+    /// The images to blend, with their optional opacity.
+    #[css(iterable)]
+    pub elements: Vec<CrossFadeElement<Image, Percentage>>,
+}
+
+/// An image in a `cross-fade()`.
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
+#[derive(Clone, Debug, MallocSizeOf, PartialEq, ToComputedValue, ToCss)]
+pub struct CrossFadeElement<Image, Percentage> {
+    /// The image to blend.
+    pub image: Image,
+    /// The opacity of this image, if specified.
+    pub percentage: Option<Percentage>,
+}
+
+/// An `image-set()` image, offering several images for the user agent to
+/// choose from depending on their resolution and type.
+/// <https://drafts.csswg.org/css-images-4/#image-set-notation>
+#[css(comma, function)]
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
+#[derive(Clone, Debug, MallocSizeOf, PartialEq, ToComputedValue, ToCss)]
+pub struct ImageSet<Image, Resolution> {
+    /// The candidate images.
+    #[css(iterable)]
+    pub items: Vec<ImageSetItem<Image, Resolution>>,
+}
+
+/// A candidate image in an `image-set()`.
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
+#[derive(Clone, Debug, MallocSizeOf, PartialEq, ToComputedValue)]
+pub struct ImageSetItem<Image, Resolution> {
+    /// The candidate image.
+    pub image: Image,
+    /// The resolution of the candidate image.
+    pub resolution: Resolution,
+    /// The MIME type from the `type()` hint, if any.
+    pub mime_type: Option<String>,
+}
+
+impl<I, R> ToCss for ImageSetItem<I, R>
+where
+    I: ToCss,
+    R: ToCss,
+{
+    fn to_css<W>(&self, dest: &mut CssWriter<W>) -> fmt::Result
+    where
+        W: Write,
+    {
+        self.image.to_css(dest)?;
+        dest.write_str(" ")?;
+        self.resolution.to_css(dest)?;
+        if let Some(ref mime_type) = self.mime_type {
+            dest.write_str(" type(")?;
+            mime_type.to_css(dest)?;
+            dest.write_str(")")?;
+        }
+        Ok(())
+    }
+}
+
 /// Specified values for a paint worklet.
 /// <https://drafts.css-houdini.org/css-paint-api/>
-#[cfg_attr(feature = "servo", derive(MallocSizeOf))]
+#[cfg_attr(feature = "servo", derive(Deserialize, MallocSizeOf, Serialize))]
 #[derive(Clone, Debug, PartialEq, ToComputedValue)]
 pub struct PaintWorklet {
     /// The name the worklet was registered with.
     pub name: Atom,
     /// The arguments for the worklet.
     /// TODO: store a parsed representation of the arguments.
+    ///
+    /// The arguments are currently raw token streams, so they are not
+    /// serialized with serde.
     #[cfg_attr(feature = "servo", ignore_malloc_size_of = "Arc")]
+    #[cfg_attr(feature = "servo", serde(skip))]
     pub arguments: Vec<Arc<custom_properties::SpecifiedValue>>,
 }
 
@@ -162,11 +280,48 @@ impl ToCss for PaintWorklet {
     }
 }
 
+/// An `image()` image, naming an image and a solid color to fall back to,
+/// at least one of which is present.
+/// <https://drafts.csswg.org/css-images-4/#image-notation>
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
+#[derive(Clone, Debug, MallocSizeOf, PartialEq, ToComputedValue)]
+pub struct ImageFunction<ImageUrl, Color> {
+    /// The image to use, if specified.
+    pub url: Option<ImageUrl>,
+    /// The color to use if the image can't be loaded, or if no image was
+    /// specified.
+    pub color: Option<Color>,
+}
+
+impl<U, C> ToCss for ImageFunction<U, C>
+where
+    U: ToCss,
+    C: ToCss,
+{
+    fn to_css<W>(&self, dest: &mut CssWriter<W>) -> fmt::Result
+    where
+        W: Write,
+    {
+        dest.write_str("image(")?;
+        if let Some(ref url) = self.url {
+            url.to_css(dest)?;
+            if self.color.is_some() {
+                dest.write_str(", ")?;
+            }
+        }
+        if let Some(ref color) = self.color {
+            color.to_css(dest)?;
+        }
+        dest.write_str(")")
+    }
+}
+
 /// Values for `moz-image-rect`.
 ///
 /// `-moz-image-rect(<uri>, top, right, bottom, left);`
 #[allow(missing_docs)]
 #[css(comma, function)]
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Debug, MallocSizeOf, PartialEq, SpecifiedValueInfo,
          ToComputedValue, ToCss)]
 pub struct MozImageRect<NumberOrPercentage, MozImageRectUrl> {
@@ -177,22 +332,30 @@ pub struct MozImageRect<NumberOrPercentage, MozImageRectUrl> {
     pub left: NumberOrPercentage,
 }
 
-impl<G, R, U> fmt::Debug for Image<G, R, U>
+impl<G, C, R, U, P, Res, Col> fmt::Debug for Image<G, C, R, U, P, Res, Col>
 where
     G: ToCss,
+    C: ToCss,
     R: ToCss,
     U: ToCss,
+    P: ToCss,
+    Res: ToCss,
+    Col: ToCss,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         self.to_css(&mut CssWriter::new(f))
     }
 }
 
-impl<G, R, U> ToCss for Image<G, R, U>
+impl<G, C, R, U, P, Res, Col> ToCss for Image<G, C, R, U, P, Res, Col>
 where
     G: ToCss,
+    C: ToCss,
     R: ToCss,
     U: ToCss,
+    P: ToCss,
+    Res: ToCss,
+    Col: ToCss,
 {
     fn to_css<W>(&self, dest: &mut CssWriter<W>) -> fmt::Result
     where
@@ -201,7 +364,10 @@ where
         match *self {
             Image::Url(ref url) => url.to_css(dest),
             Image::Gradient(ref gradient) => gradient.to_css(dest),
+            Image::ConicGradient(ref gradient) => gradient.to_css(dest),
             Image::Rect(ref rect) => rect.to_css(dest),
+            Image::CrossFade(ref cross_fade) => cross_fade.to_css(dest),
+            Image::ImageSet(ref image_set) => image_set.to_css(dest),
             #[cfg(feature = "servo")]
             Image::PaintWorklet(ref paint_worklet) => paint_worklet.to_css(dest),
             Image::Element(ref selector) => {
@@ -209,6 +375,12 @@ where
                 serialize_atom_identifier(selector, dest)?;
                 dest.write_str(")")
             },
+            Image::Image(ref image) => image.to_css(dest),
+            Image::Attr(ref attr) => {
+                dest.write_str("attr(")?;
+                attr.name_to_css(dest)?;
+                dest.write_str(" url)")
+            },
         }
     }
 }
@@ -272,6 +444,53 @@ where
                 false
             },
         };
+        if let Some(ref method) = self.color_interpolation {
+            if !skip_comma {
+                dest.write_str(" ")?;
+            }
+            method.to_css(dest)?;
+            skip_comma = false;
+        }
+        for item in &self.items {
+            if !skip_comma {
+                dest.write_str(", ")?;
+            }
+            skip_comma = false;
+            item.to_css(dest)?;
+        }
+        dest.write_str(")")
+    }
+}
+
+impl<A, P, C, AoP> ToCss for ConicGradient<A, P, C, AoP>
+where
+    A: ToCss,
+    P: ToCss,
+    C: ToCss,
+    AoP: ToCss,
+{
+    fn to_css<W>(&self, dest: &mut CssWriter<W>) -> fmt::Result
+    where
+        W: Write,
+    {
+        if self.repeating {
+            dest.write_str("repeating-")?;
+        }
+        dest.write_str("conic-gradient(")?;
+        let mut skip_comma = true;
+        if let Some(ref angle) = self.angle {
+            dest.write_str("from ")?;
+            angle.to_css(dest)?;
+            skip_comma = false;
+        }
+        if let Some(ref position) = self.position {
+            if !skip_comma {
+                dest.write_str(" ")?;
+            }
+            dest.write_str("at ")?;
+            position.to_css(dest)?;
+            skip_comma = false;
+        }
         for item in &self.items {
             if !skip_comma {
                 dest.write_str(", ")?;
@@ -0,0 +1,22 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Generic types for CSS values related to length.
+
+/// A generic value for the `width` and `height` properties, including the
+/// intrinsic sizing keywords.
+///
+/// https://drafts.csswg.org/css-sizing-3/#sizing-values
+#[derive(Clone, Debug, MallocSizeOf, PartialEq, SpecifiedValueInfo, ToComputedValue, ToCss)]
+pub enum Size<LengthOrPercentageOrAuto, LengthOrPercentage> {
+    /// `<length-percentage> | auto`
+    LengthOrPercentageOrAuto(LengthOrPercentageOrAuto),
+    /// `min-content`
+    MinContent,
+    /// `max-content`
+    MaxContent,
+    /// `fit-content(<length-percentage>)`
+    #[css(function)]
+    FitContent(LengthOrPercentage),
+}
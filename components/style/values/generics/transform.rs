@@ -325,6 +325,12 @@ impl ToAbsoluteLength for SpecifiedLength {
         match *self {
             SpecifiedLength::NoCalc(len) => len.to_computed_pixel_length_without_context(),
             SpecifiedLength::Calc(ref calc) => calc.to_computed_pixel_length_without_context(),
+            SpecifiedLength::MinMax(ref min_max) => {
+                min_max.to_computed_pixel_length_without_context()
+            },
+            SpecifiedLength::Stepped(ref stepped) => {
+                stepped.to_computed_pixel_length_without_context()
+            },
         }
     }
 }
@@ -339,6 +345,8 @@ impl ToAbsoluteLength for SpecifiedLengthOrPercentage {
         match *self {
             Length(len) => len.to_computed_pixel_length_without_context(),
             Calc(ref calc) => calc.to_computed_pixel_length_without_context(),
+            MinMax(ref min_max) => min_max.to_computed_pixel_length_without_context(),
+            Stepped(ref stepped) => stepped.to_computed_pixel_length_without_context(),
             _ => Err(()),
         }
     }
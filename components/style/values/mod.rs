@@ -27,6 +27,7 @@ pub mod computed;
 pub mod distance;
 pub mod generics;
 pub mod specified;
+pub mod units;
 
 /// A CSS float value.
 pub type CSSFloat = f32;
@@ -61,11 +62,18 @@ where
 }
 
 /// Serialize a normalized value into percentage.
+///
+/// The result is rounded to four decimal places, so that floating point error
+/// from the multiplication (e.g. `28.999998%` for `0.29`) doesn't leak into the
+/// serialization. The rounding is done in double precision, so that it doesn't
+/// overflow for huge percentages.
 pub fn serialize_percentage<W>(value: CSSFloat, dest: &mut CssWriter<W>) -> fmt::Result
 where
     W: Write,
 {
-    (value * 100.).to_css(dest)?;
+    let percent = value * 100.;
+    let rounded = ((percent as f64) * 10000.).round() / 10000.;
+    (rounded as CSSFloat).to_css(dest)?;
     dest.write_str("%")
 }
 
@@ -93,6 +101,7 @@ impl Parse for Impossible {
 }
 
 /// A struct representing one of two kinds of values.
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Animate, Clone, ComputeSquaredDistance, Copy, MallocSizeOf, PartialEq,
          SpecifiedValueInfo, ToAnimatedValue, ToAnimatedZero, ToComputedValue,
          ToCss)]
@@ -13,7 +13,9 @@ use std::fmt::{self, Write};
 use style_traits::{CssWriter, ParseError, StyleParseErrorKind, ToCss};
 use values::{CustomIdent, KeyframesName};
 use values::generics::box_::AnimationIterationCount as GenericAnimationIterationCount;
+use values::generics::box_::AspectRatio as GenericAspectRatio;
 use values::generics::box_::Perspective as GenericPerspective;
+use values::generics::box_::Ratio as GenericRatio;
 use values::generics::box_::VerticalAlign as GenericVerticalAlign;
 use values::specified::{AllowQuirks, Number};
 use values::specified::length::{LengthOrPercentage, NonNegativeLength};
@@ -714,3 +716,43 @@ impl Parse for Perspective {
         )?))
     }
 }
+
+/// A specified `<ratio>` value.
+pub type Ratio = GenericRatio<Number>;
+
+impl Parse for Ratio {
+    fn parse<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i>> {
+        let first = Number::parse_non_negative(context, input)?;
+        // The second number defaults to 1 when omitted, so `1.5` means
+        // `1.5 / 1`.
+        if input.try(|i| i.expect_delim('/')).is_err() {
+            return Ok(GenericRatio(first, Number::new(1.)));
+        }
+        let second = Number::parse_non_negative(context, input)?;
+        Ok(GenericRatio(first, second))
+    }
+}
+
+/// A specified value for the `aspect-ratio` property.
+pub type AspectRatio = GenericAspectRatio<Number>;
+
+impl Parse for AspectRatio {
+    /// auto || <ratio>
+    fn parse<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i>> {
+        let mut auto = input.try(|i| i.expect_ident_matching("auto")).is_ok();
+        let ratio = input.try(|i| Ratio::parse(context, i)).ok();
+        if !auto && ratio.is_some() {
+            auto = input.try(|i| i.expect_ident_matching("auto")).is_ok();
+        }
+        if !auto && ratio.is_none() {
+            return Err(input.new_custom_error(StyleParseErrorKind::UnspecifiedError));
+        }
+        Ok(GenericAspectRatio { auto, ratio })
+    }
+}
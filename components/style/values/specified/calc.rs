@@ -8,6 +8,7 @@
 
 use cssparser::{AngleOrNumber, NumberOrPercentage, Parser, Token};
 use parser::ParserContext;
+use std::f32;
 use std::fmt::{self, Write};
 use style_traits::{CssWriter, ParseError, SpecifiedValueInfo, StyleParseErrorKind, ToCss};
 use style_traits::values::specified::AllowedNumericType;
@@ -17,6 +18,21 @@ use values::specified::{Angle, Time};
 use values::specified::length::{AbsoluteLength, FontRelativeLength, NoCalcLength};
 use values::specified::length::ViewportPercentageLength;
 
+/// Clamps the result of a calc() expression to a finite value.
+///
+/// Sums and products can overflow to infinity, or to NaN when infinities
+/// cancel out, which the rest of the style system can't deal with. Infinities
+/// saturate to the largest finite values, and NaN becomes zero.
+///
+/// <https://drafts.csswg.org/css-values-4/#calc-ieee>
+#[inline]
+fn clamp_to_finite(value: CSSFloat) -> CSSFloat {
+    if value.is_nan() {
+        return 0.;
+    }
+    value.min(f32::MAX).max(f32::MIN)
+}
+
 /// A node inside a `Calc` expression's AST.
 #[derive(Clone, Debug)]
 pub enum CalcNode {
@@ -67,6 +83,7 @@ pub enum CalcUnit {
 /// relative lengths, and to_computed_pixel_length_without_context() handles
 /// this case. Therefore, if you want to add a new field, please make sure this
 /// function work properly.
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Copy, Debug, Default, MallocSizeOf, PartialEq)]
 #[allow(missing_docs)]
 pub struct CalcLengthOrPercentage {
@@ -85,10 +102,23 @@ pub struct CalcLengthOrPercentage {
 
 impl ToCss for CalcLengthOrPercentage {
     /// <https://drafts.csswg.org/css-values/#calc-serialize>
+    fn to_css<W>(&self, dest: &mut CssWriter<W>) -> fmt::Result
+    where
+        W: Write,
+    {
+        dest.write_str("calc(")?;
+        self.sum_to_css(dest)?;
+        dest.write_str(")")
+    }
+}
+
+impl CalcLengthOrPercentage {
+    /// Serializes the terms of this expression, without the enclosing
+    /// `calc()`.
     ///
     /// FIXME(emilio): Should this simplify away zeros?
     #[allow(unused_assignments)]
-    fn to_css<W>(&self, dest: &mut CssWriter<W>) -> fmt::Result
+    fn sum_to_css<W>(&self, dest: &mut CssWriter<W>) -> fmt::Result
     where
         W: Write,
     {
@@ -129,8 +159,6 @@ impl ToCss for CalcLengthOrPercentage {
             };
         }
 
-        dest.write_str("calc(")?;
-
         // NOTE(emilio): Percentages first because of web-compat problems, see:
         // https://github.com/w3c/csswg-drafts/issues/1731
         if let Some(val) = self.percentage {
@@ -145,13 +173,305 @@ impl ToCss for CalcLengthOrPercentage {
         serialize!(em, ex);
         serialize_abs!(In, Mm, Pc, Pt, Px, Q);
         serialize!(rem, vh, vmax, vmin, vw);
+        Ok(())
+    }
+}
 
-        dest.write_str(")")
+impl CalcLengthOrPercentage {
+    /// Whether this expression has font-relative terms, and thus depends on
+    /// the font of the element.
+    #[inline]
+    pub fn is_font_relative(&self) -> bool {
+        self.em.is_some() || self.ex.is_some() || self.ch.is_some() || self.rem.is_some()
+    }
+
+    /// Whether this expression has viewport-relative terms, and thus depends
+    /// on the viewport size.
+    #[inline]
+    pub fn is_viewport_relative(&self) -> bool {
+        self.vw.is_some() || self.vh.is_some() || self.vmin.is_some() || self.vmax.is_some()
+    }
+
+    /// Clamps all the terms of this expression to finite values.
+    fn clamp_to_finite(&mut self) {
+        if let Some(ref mut absolute) = self.absolute {
+            match *absolute {
+                AbsoluteLength::Px(ref mut value) |
+                AbsoluteLength::In(ref mut value) |
+                AbsoluteLength::Cm(ref mut value) |
+                AbsoluteLength::Mm(ref mut value) |
+                AbsoluteLength::Q(ref mut value) |
+                AbsoluteLength::Pt(ref mut value) |
+                AbsoluteLength::Pc(ref mut value) => *value = clamp_to_finite(*value),
+            }
+        }
+        for value in &mut [
+            &mut self.vw,
+            &mut self.vh,
+            &mut self.vmin,
+            &mut self.vmax,
+            &mut self.em,
+            &mut self.ex,
+            &mut self.ch,
+            &mut self.rem,
+        ] {
+            if let Some(ref mut value) = **value {
+                *value = clamp_to_finite(*value);
+            }
+        }
+        if let Some(ref mut percentage) = self.percentage {
+            percentage.0 = clamp_to_finite(percentage.0);
+        }
     }
 }
 
 impl SpecifiedValueInfo for CalcLengthOrPercentage {}
 
+/// The comparison function of a `MinMaxLengthOrPercentage`.
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
+#[derive(Clone, Copy, Debug, Eq, Hash, MallocSizeOf, PartialEq, ToCss)]
+pub enum MinMaxOp {
+    /// `min()`
+    Min,
+    /// `max()`
+    Max,
+    /// `clamp()`
+    Clamp,
+}
+
+impl MinMaxOp {
+    /// Returns the function with the given name, if any.
+    pub fn from_function_name(name: &str) -> Result<Self, ()> {
+        Ok(match_ignore_ascii_case! { name,
+            "min" => MinMaxOp::Min,
+            "max" => MinMaxOp::Max,
+            "clamp" => MinMaxOp::Clamp,
+            _ => return Err(())
+        })
+    }
+
+    /// Returns the value this function evaluates to among `values`, which
+    /// are ordered by `key`.
+    ///
+    /// For `clamp()`, `values` are the minimum, central and maximum values,
+    /// in that order.
+    pub fn select<T, I, K>(self, values: I, key: K) -> T
+    where
+        I: IntoIterator<Item = T>,
+        K: Fn(&T) -> CSSFloat,
+    {
+        let mut values = values.into_iter();
+        let first = values.next().expect("Math functions have at least one argument");
+        match self {
+            MinMaxOp::Min => values.fold(first, |a, b| if key(&b) < key(&a) { b } else { a }),
+            MinMaxOp::Max => values.fold(first, |a, b| if key(&b) > key(&a) { b } else { a }),
+            MinMaxOp::Clamp => {
+                let center = values.next().expect("clamp() has three arguments");
+                let max = values.next().expect("clamp() has three arguments");
+                // clamp(MIN, VAL, MAX) is max(MIN, min(VAL, MAX)), so the
+                // minimum wins over the maximum if they overlap.
+                let value = if key(&max) < key(&center) { max } else { center };
+                if key(&value) < key(&first) { first } else { value }
+            },
+        }
+    }
+}
+
+/// The maximum number of distinct percentages among the arguments of a
+/// `min()` or `max()` function.
+///
+/// Arguments with the same percentage are compared at computed value time,
+/// but the others can only be compared at used value time, once percentages
+/// are resolved, so the computed value needs to keep them around.
+pub const MAX_MIN_MAX_PERCENTAGES: usize = 3;
+
+/// A `min()`, `max()` or `clamp()` function, whose arguments are `<length>`
+/// or `<length-percentage>` sums.
+///
+/// Arguments with the same percentage are compared at computed value time.
+/// If their percentages differ, like in `min(10px, 50%)`, the comparison is
+/// deferred to used value time, like the percentage of a `calc()`
+/// expression is.
+///
+/// <https://drafts.csswg.org/css-values-4/#comp-func>
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
+#[derive(Clone, Debug, MallocSizeOf, PartialEq)]
+pub struct MinMaxLengthOrPercentage {
+    /// The comparison function.
+    pub op: MinMaxOp,
+    /// The arguments of the function, three of them for `clamp()`.
+    pub args: Box<[CalcLengthOrPercentage]>,
+}
+
+impl ToCss for MinMaxLengthOrPercentage {
+    fn to_css<W>(&self, dest: &mut CssWriter<W>) -> fmt::Result
+    where
+        W: Write,
+    {
+        self.op.to_css(dest)?;
+        dest.write_str("(")?;
+        for (i, arg) in self.args.iter().enumerate() {
+            if i != 0 {
+                dest.write_str(", ")?;
+            }
+            arg.sum_to_css(dest)?;
+        }
+        dest.write_str(")")
+    }
+}
+
+impl SpecifiedValueInfo for MinMaxLengthOrPercentage {}
+
+/// The `<rounding-strategy>` of a `round()` function.
+///
+/// <https://drafts.csswg.org/css-values-4/#typedef-rounding-strategy>
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
+#[derive(Clone, Copy, Debug, Eq, MallocSizeOf, Parse, PartialEq, ToCss)]
+pub enum RoundingStrategy {
+    /// Rounds to the closest multiple, or up if both are as close.
+    Nearest,
+    /// Rounds up, towards positive infinity.
+    Up,
+    /// Rounds down, towards negative infinity.
+    Down,
+    /// Rounds towards zero.
+    ToZero,
+}
+
+/// The function of a `SteppedLength`.
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
+#[derive(Clone, Copy, Debug, MallocSizeOf, PartialEq)]
+pub enum SteppedOp {
+    /// `round()`
+    Round(RoundingStrategy),
+    /// `mod()`
+    Mod,
+    /// `rem()`
+    Rem,
+}
+
+impl SteppedOp {
+    /// Returns the function with the given name, if any.
+    ///
+    /// `round()` starts with the default rounding strategy, which may be
+    /// overridden by its first argument.
+    pub fn from_function_name(name: &str) -> Result<Self, ()> {
+        Ok(match_ignore_ascii_case! { name,
+            "round" => SteppedOp::Round(RoundingStrategy::Nearest),
+            "mod" => SteppedOp::Mod,
+            "rem" => SteppedOp::Rem,
+            _ => return Err(())
+        })
+    }
+
+    /// Returns the value this function evaluates to, given its value and
+    /// step arguments.
+    ///
+    /// A zero step makes the result NaN, which is clamped to zero like for
+    /// any other calc() expression.
+    pub fn apply(self, value: CSSFloat, step: CSSFloat) -> CSSFloat {
+        clamp_to_finite(match self {
+            SteppedOp::Round(strategy) => {
+                let step = step.abs();
+                let lower = (value / step).floor() * step;
+                let upper = (value / step).ceil() * step;
+                match strategy {
+                    RoundingStrategy::Nearest => {
+                        if value - lower < upper - value { lower } else { upper }
+                    },
+                    RoundingStrategy::Up => upper,
+                    RoundingStrategy::Down => lower,
+                    RoundingStrategy::ToZero => if value < 0. { upper } else { lower },
+                }
+            },
+            // The result has the sign of the step.
+            SteppedOp::Mod => value - step * (value / step).floor(),
+            // The result has the sign of the value.
+            SteppedOp::Rem => value % step,
+        })
+    }
+}
+
+impl ToCss for SteppedOp {
+    fn to_css<W>(&self, dest: &mut CssWriter<W>) -> fmt::Result
+    where
+        W: Write,
+    {
+        dest.write_str(match *self {
+            SteppedOp::Round(..) => "round",
+            SteppedOp::Mod => "mod",
+            SteppedOp::Rem => "rem",
+        })
+    }
+}
+
+/// A `round()`, `mod()` or `rem()` function, whose arguments are `<length>`
+/// sums.
+///
+/// Percentages aren't allowed, since the result could only be computed at
+/// used value time.
+///
+/// <https://drafts.csswg.org/css-values-4/#round-func>
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
+#[derive(Clone, Debug, MallocSizeOf, PartialEq)]
+pub struct SteppedLength {
+    /// The stepped-value function.
+    pub op: SteppedOp,
+    /// The value to round, or the dividend.
+    pub value: CalcLengthOrPercentage,
+    /// The rounding interval, or the divisor.
+    pub step: CalcLengthOrPercentage,
+}
+
+impl ToCss for SteppedLength {
+    fn to_css<W>(&self, dest: &mut CssWriter<W>) -> fmt::Result
+    where
+        W: Write,
+    {
+        self.op.to_css(dest)?;
+        dest.write_str("(")?;
+        match self.op {
+            SteppedOp::Round(RoundingStrategy::Nearest) => {},
+            SteppedOp::Round(strategy) => {
+                strategy.to_css(dest)?;
+                dest.write_str(", ")?;
+            },
+            SteppedOp::Mod | SteppedOp::Rem => {},
+        }
+        self.value.sum_to_css(dest)?;
+        dest.write_str(", ")?;
+        self.step.sum_to_css(dest)?;
+        dest.write_str(")")
+    }
+}
+
+impl SpecifiedValueInfo for SteppedLength {}
+
+/// A math function that a `<length>` or `<length-percentage>` can be
+/// specified with.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MathFunction {
+    /// `calc()`
+    Calc,
+    /// `min()`, `max()` or `clamp()`
+    MinMax(MinMaxOp),
+    /// `round()`, `mod()` or `rem()`
+    Stepped(SteppedOp),
+}
+
+impl MathFunction {
+    /// Returns the function with the given name, if any.
+    pub fn from_function_name(name: &str) -> Result<Self, ()> {
+        if name.eq_ignore_ascii_case("calc") {
+            return Ok(MathFunction::Calc);
+        }
+        if let Ok(op) = MinMaxOp::from_function_name(name) {
+            return Ok(MathFunction::MinMax(op));
+        }
+        SteppedOp::from_function_name(name).map(MathFunction::Stepped)
+    }
+}
+
 impl CalcNode {
     /// Tries to parse a single element in the expression, that is, a
     /// `<length>`, `<angle>`, `<time>`, `<percentage>`, according to
@@ -308,6 +628,7 @@ impl CalcNode {
             ..Default::default()
         };
         self.add_length_or_percentage_to(&mut ret, 1.0)?;
+        ret.clamp_to_finite();
         Ok(ret)
     }
 
@@ -534,7 +855,16 @@ impl CalcNode {
     ) -> Result<CSSInteger, ParseError<'i>> {
         Self::parse(context, input, CalcUnit::Integer)?
             .to_number()
-            .map(|n| n as CSSInteger)
+            .map(|n| {
+                let n = clamp_to_finite(n);
+                if n >= CSSInteger::max_value() as CSSFloat {
+                    CSSInteger::max_value()
+                } else if n <= CSSInteger::min_value() as CSSFloat {
+                    CSSInteger::min_value()
+                } else {
+                    n as CSSInteger
+                }
+            })
             .map_err(|()| input.new_custom_error(StyleParseErrorKind::UnspecifiedError))
     }
 
@@ -549,6 +879,97 @@ impl CalcNode {
             .map_err(|()| input.new_custom_error(StyleParseErrorKind::UnspecifiedError))
     }
 
+    /// Parses the arguments of a `min()`, `max()` or `clamp()` function, each
+    /// of them being a sum of `expected_unit`.
+    fn parse_min_max<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+        op: MinMaxOp,
+        expected_unit: CalcUnit,
+        clamping_mode: AllowedNumericType,
+    ) -> Result<MinMaxLengthOrPercentage, ParseError<'i>> {
+        let args = input.parse_comma_separated(|i| {
+            Self::parse(context, i, expected_unit)?
+                .to_length_or_percentage(clamping_mode)
+                .map_err(|()| i.new_custom_error(StyleParseErrorKind::UnspecifiedError))
+        })?;
+
+        if op == MinMaxOp::Clamp && args.len() != 3 {
+            return Err(input.new_custom_error(StyleParseErrorKind::UnspecifiedError));
+        }
+
+        let mut percentages = Vec::with_capacity(args.len());
+        for arg in args.iter() {
+            let percentage = arg.percentage.map_or(0., |p| p.0);
+            if !percentages.contains(&percentage) {
+                percentages.push(percentage);
+            }
+        }
+
+        // Gecko's calc() values can't represent a comparison that depends on
+        // the percentage basis.
+        #[cfg(feature = "gecko")]
+        {
+            if percentages.len() > 1 {
+                return Err(input.new_custom_error(StyleParseErrorKind::UnspecifiedError));
+            }
+        }
+
+        if percentages.len() > MAX_MIN_MAX_PERCENTAGES {
+            return Err(input.new_custom_error(StyleParseErrorKind::UnspecifiedError));
+        }
+
+        Ok(MinMaxLengthOrPercentage {
+            op,
+            args: args.into_boxed_slice(),
+        })
+    }
+
+    /// Convenience parsing function for `min()`, `max()` and `clamp()` of
+    /// `<length> | <percentage>`.
+    pub fn parse_length_or_percentage_min_max<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+        op: MinMaxOp,
+        clamping_mode: AllowedNumericType,
+    ) -> Result<MinMaxLengthOrPercentage, ParseError<'i>> {
+        Self::parse_min_max(context, input, op, CalcUnit::LengthOrPercentage, clamping_mode)
+    }
+
+    /// Convenience parsing function for `min()`, `max()` and `clamp()` of
+    /// `<length>`.
+    pub fn parse_length_min_max<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+        op: MinMaxOp,
+        clamping_mode: AllowedNumericType,
+    ) -> Result<MinMaxLengthOrPercentage, ParseError<'i>> {
+        Self::parse_min_max(context, input, op, CalcUnit::Length, clamping_mode)
+    }
+
+    /// Convenience parsing function for the arguments of `round()`, `mod()`
+    /// and `rem()` functions of `<length>`.
+    pub fn parse_length_stepped<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+        mut op: SteppedOp,
+        clamping_mode: AllowedNumericType,
+    ) -> Result<SteppedLength, ParseError<'i>> {
+        if let SteppedOp::Round(..) = op {
+            if let Ok(strategy) = input.try(RoundingStrategy::parse) {
+                input.expect_comma()?;
+                op = SteppedOp::Round(strategy);
+            }
+        }
+
+        let value = Self::parse_length(context, input, clamping_mode)?;
+        input.expect_comma()?;
+        // Only the result is clamped, not the step it's computed with.
+        let step = Self::parse_length(context, input, AllowedNumericType::All)?;
+
+        Ok(SteppedLength { op, value, step })
+    }
+
     /// Convenience parsing function for percentages.
     pub fn parse_percentage<'i, 't>(
         context: &ParserContext,
@@ -556,6 +977,7 @@ impl CalcNode {
     ) -> Result<CSSFloat, ParseError<'i>> {
         Self::parse(context, input, CalcUnit::Percentage)?
             .to_percentage()
+            .map(clamp_to_finite)
             .map_err(|()| input.new_custom_error(StyleParseErrorKind::UnspecifiedError))
     }
 
@@ -597,6 +1019,11 @@ impl CalcNode {
     ) -> Result<Time, ParseError<'i>> {
         Self::parse(context, input, CalcUnit::Time)?
             .to_time()
+            .map(|time| if time.seconds().is_finite() {
+                time
+            } else {
+                Time::from_calc(clamp_to_finite(time.seconds()))
+            })
             .map_err(|()| input.new_custom_error(StyleParseErrorKind::UnspecifiedError))
     }
 
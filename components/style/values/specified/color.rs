@@ -21,7 +21,8 @@ use values::computed::{Color as ComputedColor, Context, ToComputedValue};
 use values::specified::calc::CalcNode;
 
 /// Specified color value
-#[derive(Clone, Debug, MallocSizeOf, PartialEq)]
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
+#[derive(Clone, Debug, MallocSizeOf)]
 pub enum Color {
     /// The 'currentColor' keyword
     CurrentColor,
@@ -58,6 +59,27 @@ mod gecko {
     }
 }
 
+/// Colors compare equal when they represent the same value, regardless of how
+/// they were authored, so `red` and `#ff0000` are equal.
+impl PartialEq for Color {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (&Color::CurrentColor, &Color::CurrentColor) => true,
+            (&Color::Numeric { parsed: ref a, .. }, &Color::Numeric { parsed: ref b, .. }) => {
+                a == b
+            },
+            (&Color::Complex(ref a), &Color::Complex(ref b)) => a == b,
+            #[cfg(feature = "gecko")]
+            (&Color::System(ref a), &Color::System(ref b)) => a == b,
+            #[cfg(feature = "gecko")]
+            (&Color::Special(ref a), &Color::Special(ref b)) => a == b,
+            #[cfg(feature = "gecko")]
+            (&Color::InheritFromBodyQuirk, &Color::InheritFromBodyQuirk) => true,
+            _ => false,
+        }
+    }
+}
+
 impl From<RGBA> for Color {
     fn from(value: RGBA) -> Self {
         Color::rgba(value)
@@ -133,20 +155,29 @@ impl Parse for Color {
         context: &ParserContext,
         input: &mut Parser<'i, 't>,
     ) -> Result<Self, ParseError<'i>> {
-        // Currently we only store authored value for color keywords,
-        // because all browsers serialize those values as keywords for
-        // specified value.
+        // We store the authored value so that the specified value serializes
+        // back the way it was written. Color keywords are lowercased, because
+        // all browsers serialize those values as keywords for specified value,
+        // while hex and functional notations are kept verbatim.
+        input.skip_whitespace();
         let start = input.state();
-        let authored = input.expect_ident_cloned().ok();
+        let keyword = input.expect_ident().ok().map(|ident| ident.to_ascii_lowercase());
         input.reset(&start);
+        let start_position = input.position();
 
         let compontent_parser = ColorComponentParser(&*context);
         match input.try(|i| CSSParserColor::parse_with(&compontent_parser, i)) {
             Ok(value) => Ok(match value {
                 CSSParserColor::CurrentColor => Color::CurrentColor,
-                CSSParserColor::RGBA(rgba) => Color::Numeric {
-                    parsed: rgba,
-                    authored: authored.map(|s| s.to_ascii_lowercase().into_boxed_str()),
+                CSSParserColor::RGBA(rgba) => {
+                    let authored = match keyword {
+                        Some(keyword) => keyword,
+                        None => input.slice_from(start_position).to_owned(),
+                    };
+                    Color::Numeric {
+                        parsed: rgba,
+                        authored: Some(authored.into_boxed_str()),
+                    }
                 },
             }),
             Err(e) => {
@@ -395,6 +426,7 @@ impl ToComputedValue for Color {
 
 /// Specified color value, but resolved to just RGBA for computed value
 /// with value from color property at the same context.
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Debug, MallocSizeOf, PartialEq, SpecifiedValueInfo, ToCss)]
 pub struct RGBAColor(pub Color);
 
@@ -470,3 +502,103 @@ impl Parse for ColorPropertyValue {
         Color::parse_quirky(context, input, AllowQuirks::Yes).map(ColorPropertyValue)
     }
 }
+
+/// A color space that a gradient can be interpolated in.
+///
+/// <https://drafts.csswg.org/css-color-4/#interpolation-space>
+#[allow(missing_docs)]
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
+#[derive(Clone, Copy, Debug, Eq, MallocSizeOf, Parse, PartialEq,
+         SpecifiedValueInfo, ToComputedValue, ToCss)]
+pub enum ColorSpace {
+    Srgb,
+    SrgbLinear,
+    DisplayP3,
+    A98Rgb,
+    ProphotoRgb,
+    Rec2020,
+    Lab,
+    Oklab,
+    Xyz,
+    XyzD50,
+    XyzD65,
+    Hsl,
+    Hwb,
+    Lch,
+    Oklch,
+}
+
+impl ColorSpace {
+    /// Returns whether this is a polar color space, i.e. one that has a hue
+    /// component and thus accepts a hue interpolation method.
+    #[inline]
+    pub fn is_polar(&self) -> bool {
+        match *self {
+            ColorSpace::Hsl | ColorSpace::Hwb | ColorSpace::Lch | ColorSpace::Oklch => true,
+            _ => false,
+        }
+    }
+}
+
+/// How the hue component is interpolated in a polar color space.
+///
+/// <https://drafts.csswg.org/css-color-4/#hue-interpolation>
+#[allow(missing_docs)]
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
+#[derive(Clone, Copy, Debug, Eq, MallocSizeOf, Parse, PartialEq,
+         SpecifiedValueInfo, ToComputedValue, ToCss)]
+pub enum HueInterpolationMethod {
+    Shorter,
+    Longer,
+    Increasing,
+    Decreasing,
+}
+
+/// A `<color-interpolation-method>` value: `in <color-space> [<hue> hue]?`.
+///
+/// <https://drafts.csswg.org/css-color-4/#color-interpolation-method>
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
+#[derive(Clone, Copy, Debug, Eq, MallocSizeOf, PartialEq, SpecifiedValueInfo,
+         ToComputedValue)]
+pub struct ColorInterpolationMethod {
+    /// The color space to interpolate in.
+    pub space: ColorSpace,
+    /// The hue interpolation method, only valid for polar color spaces.
+    pub hue: Option<HueInterpolationMethod>,
+}
+
+impl Parse for ColorInterpolationMethod {
+    fn parse<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i>> {
+        input.expect_ident_matching("in")?;
+        let space = ColorSpace::parse(context, input)?;
+        let hue = if space.is_polar() {
+            input.try(|i| -> Result<_, ParseError<'i>> {
+                let hue = HueInterpolationMethod::parse(context, i)?;
+                i.expect_ident_matching("hue")?;
+                Ok(hue)
+            }).ok()
+        } else {
+            None
+        };
+        Ok(ColorInterpolationMethod { space, hue })
+    }
+}
+
+impl ToCss for ColorInterpolationMethod {
+    fn to_css<W>(&self, dest: &mut CssWriter<W>) -> fmt::Result
+    where
+        W: Write,
+    {
+        dest.write_str("in ")?;
+        self.space.to_css(dest)?;
+        if let Some(ref hue) = self.hue {
+            dest.write_str(" ")?;
+            hue.to_css(dest)?;
+            dest.write_str(" hue")?;
+        }
+        Ok(())
+    }
+}
@@ -918,6 +918,22 @@ impl FontSize {
                     .unwrap()
                     .into()
             },
+            FontSize::Length(LengthOrPercentage::MinMax(ref min_max)) => {
+                // The selected argument may change along with the parent font,
+                // so the result is never considered keyword-derived.
+                let calc = min_max.to_computed_value_zoomed(context, base_size);
+                calc.to_used_value(Some(base_size.resolve(context)))
+                    .unwrap()
+                    .into()
+            },
+            FontSize::Length(LengthOrPercentage::Stepped(ref stepped)) => {
+                // Rounding doesn't scale along with the parent font, so the
+                // result is never considered keyword-derived either.
+                let calc = stepped.to_computed_value_zoomed(context, base_size);
+                calc.to_used_value(Some(base_size.resolve(context)))
+                    .unwrap()
+                    .into()
+            },
             FontSize::Keyword(i) => {
                 // As a specified keyword, this is keyword derived
                 info = Some(i);
@@ -0,0 +1,87 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Frequency values:
+//!
+//! https://drafts.csswg.org/css-values/#frequency
+
+use cssparser::{Parser, Token};
+use parser::{Parse, ParserContext};
+use std::fmt::{self, Write};
+use style_traits::{CssWriter, ParseError, StyleParseErrorKind, ToCss};
+use style_traits::values::specified::AllowedNumericType;
+use values::CSSFloat;
+
+/// A specified frequency, normalized to hertz.
+#[derive(Clone, Copy, Debug, MallocSizeOf, PartialEq, PartialOrd, SpecifiedValueInfo,
+         ToComputedValue)]
+pub struct Frequency(CSSFloat);
+
+impl Frequency {
+    /// Returns a frequency value that represents `hz` hertz.
+    pub fn from_hz(hz: CSSFloat) -> Self {
+        Frequency(hz)
+    }
+
+    /// Returns the frequency in hertz.
+    pub fn hz(self) -> CSSFloat {
+        self.0
+    }
+
+    /// Parse a `<frequency>` value given a value and an unit.
+    pub fn parse_dimension(value: CSSFloat, unit: &str) -> Result<Frequency, ()> {
+        let hz = match_ignore_ascii_case! { unit,
+            "hz" => value,
+            "khz" => value * 1000.,
+            _ => return Err(())
+        };
+        Ok(Frequency(hz))
+    }
+
+    fn parse_with_clamping_mode<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+        clamping_mode: AllowedNumericType,
+    ) -> Result<Self, ParseError<'i>> {
+        let location = input.current_source_location();
+        match *input.next()? {
+            Token::Dimension {
+                value, ref unit, ..
+            } if clamping_mode.is_ok(context.parsing_mode, value) => {
+                Frequency::parse_dimension(value, unit)
+                    .map_err(|()| location.new_custom_error(
+                        StyleParseErrorKind::UnexpectedDimension(unit.clone())
+                    ))
+            },
+            ref t => Err(location.new_unexpected_token_error(t.clone())),
+        }
+    }
+
+    /// Parses a non-negative frequency value.
+    pub fn parse_non_negative<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i>> {
+        Self::parse_with_clamping_mode(context, input, AllowedNumericType::NonNegative)
+    }
+}
+
+impl Parse for Frequency {
+    fn parse<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i>> {
+        Self::parse_with_clamping_mode(context, input, AllowedNumericType::All)
+    }
+}
+
+impl ToCss for Frequency {
+    fn to_css<W>(&self, dest: &mut CssWriter<W>) -> fmt::Result
+    where
+        W: Write,
+    {
+        self.0.to_css(dest)?;
+        dest.write_str("Hz")
+    }
+}
@@ -8,7 +8,7 @@
 //! [image]: https://drafts.csswg.org/css-images/#image-values
 
 use Atom;
-use cssparser::{Parser, Token};
+use cssparser::{Delimiter, Parser, Token};
 use custom_properties::SpecifiedValue;
 use parser::{Parse, ParserContext};
 use selectors::parser::SelectorParseErrorKind;
@@ -18,15 +18,17 @@ use std::cmp::Ordering;
 use std::f32::consts::PI;
 use std::fmt::{self, Write};
 use style_traits::{CssType, CssWriter, KeywordsCollectFn, ParseError};
-use style_traits::{StyleParseErrorKind, SpecifiedValueInfo, ToCss};
+use style_traits::{StyleParseErrorKind, SpecifiedValueInfo, ToCss, ValueParseErrorKind};
 use values::{Either, None_};
 #[cfg(feature = "gecko")]
 use values::computed::{Context, Position as ComputedPosition, ToComputedValue};
 use values::generics::image::{self as generic, Circle, CompatMode, Ellipse, ShapeExtent};
 use values::generics::image::PaintWorklet;
 use values::generics::position::Position as GenericPosition;
-use values::specified::{Angle, Color, Length, LengthOrPercentage};
-use values::specified::{Number, NumberOrPercentage, Percentage, RGBAColor};
+use values::specified::{Angle, Color, ColorInterpolationMethod, Length, LengthOrPercentage};
+use values::specified::{Number, NumberOrPercentage, Percentage, RGBAColor, Resolution};
+#[cfg(feature = "servo")]
+use values::specified::Attr;
 use values::specified::position::{LegacyPosition, Position, PositionComponent, Side, X, Y};
 use values::specified::url::SpecifiedImageUrl;
 
@@ -35,7 +37,15 @@ pub type ImageLayer = Either<None_, Image>;
 
 /// Specified values for an image according to CSS-IMAGES.
 /// <https://drafts.csswg.org/css-images/#image-values>
-pub type Image = generic::Image<Gradient, MozImageRect, SpecifiedImageUrl>;
+pub type Image = generic::Image<
+    Gradient,
+    ConicGradient,
+    MozImageRect,
+    SpecifiedImageUrl,
+    Percentage,
+    Resolution,
+    RGBAColor,
+>;
 
 /// Specified values for a CSS gradient.
 /// <https://drafts.csswg.org/css-images/#gradients>
@@ -89,6 +99,7 @@ pub type GradientKind =
     generic::GradientKind<LineDirection, Length, LengthOrPercentage, GradientPosition, Angle>;
 
 /// A specified gradient line direction.
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Debug, MallocSizeOf, PartialEq)]
 pub enum LineDirection {
     /// An angular direction.
@@ -117,6 +128,34 @@ pub enum GradientPosition {
     Legacy(LegacyPosition),
 }
 
+/// Specified values for a CSS conic gradient.
+/// <https://drafts.csswg.org/css-images-4/#conic-gradients>
+pub type ConicGradient =
+    generic::ConicGradient<Angle, Position, RGBAColor, Either<Angle, Percentage>>;
+
+impl SpecifiedValueInfo for ConicGradient {
+    const SUPPORTED_TYPES: u8 = CssType::GRADIENT;
+
+    fn collect_completion_keywords(f: KeywordsCollectFn) {
+        f(&["conic-gradient", "repeating-conic-gradient"]);
+    }
+}
+
+/// Specified values for a `cross-fade()` image.
+pub type CrossFade = generic::CrossFade<Image, Percentage>;
+
+/// A specified image in a `cross-fade()`.
+pub type CrossFadeElement = generic::CrossFadeElement<Image, Percentage>;
+
+/// Specified values for an `image-set()` image.
+pub type ImageSet = generic::ImageSet<Image, Resolution>;
+
+/// A specified candidate image in an `image-set()`.
+pub type ImageSetItem = generic::ImageSetItem<Image, Resolution>;
+
+/// Specified values for an `image()` image.
+pub type ImageFunction = generic::ImageFunction<SpecifiedImageUrl, RGBAColor>;
+
 /// A specified ending shape.
 pub type EndingShape = generic::EndingShape<Length, LengthOrPercentage>;
 
@@ -135,7 +174,7 @@ impl Parse for Image {
         context: &ParserContext,
         input: &mut Parser<'i, 't>,
     ) -> Result<Image, ParseError<'i>> {
-        if let Ok(url) = input.try(|input| SpecifiedImageUrl::parse(context, input)) {
+        if let Ok(url) = input.try(|input| Image::parse_url(context, input)) {
             return Ok(generic::Image::Url(url));
         }
         if let Ok(gradient) = input.try(|i| Gradient::parse(context, i)) {
@@ -143,9 +182,24 @@ impl Parse for Image {
         }
         #[cfg(feature = "servo")]
         {
+            if let Ok(gradient) = input.try(|i| ConicGradient::parse(context, i)) {
+                return Ok(generic::Image::ConicGradient(Box::new(gradient)));
+            }
+            if let Ok(cross_fade) = input.try(|i| CrossFade::parse(context, i)) {
+                return Ok(generic::Image::CrossFade(Box::new(cross_fade)));
+            }
+            if let Ok(image_set) = input.try(|i| ImageSet::parse(context, i)) {
+                return Ok(generic::Image::ImageSet(Box::new(image_set)));
+            }
             if let Ok(paint_worklet) = input.try(|i| PaintWorklet::parse(context, i)) {
                 return Ok(generic::Image::PaintWorklet(paint_worklet));
             }
+            if let Ok(image) = input.try(|i| ImageFunction::parse(context, i)) {
+                return Ok(generic::Image::Image(Box::new(image)));
+            }
+            if let Ok(attr) = input.try(|i| Image::parse_attr(context, i)) {
+                return Ok(generic::Image::Attr(attr));
+            }
         }
         if let Ok(image_rect) = input.try(|input| MozImageRect::parse(context, input)) {
             return Ok(generic::Image::Rect(Box::new(image_rect)));
@@ -163,6 +217,47 @@ impl Image {
         generic::Image::Url(CssUrl::for_cascade(url))
     }
 
+    /// Parses an `attr(<attr-name> url)` image.
+    ///
+    /// The `url` type is required, since the default `string` type can't be
+    /// used as an image.
+    #[cfg(feature = "servo")]
+    fn parse_attr<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Attr, ParseError<'i>> {
+        input.expect_function_matching("attr")?;
+        input.parse_nested_block(|i| {
+            let attr = Attr::parse_function(context, i)?;
+            i.expect_ident_matching("url")?;
+            Ok(attr)
+        })
+    }
+
+    /// Parses an image `url()`.
+    ///
+    /// Servo resolves urls eagerly, so an url that can't be resolved makes the
+    /// image invalid.
+    #[cfg(feature = "servo")]
+    fn parse_url<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<SpecifiedImageUrl, ParseError<'i>> {
+        let location = input.current_source_location();
+        let url = input.expect_url()?;
+        SpecifiedImageUrl::try_parse_from_string(url.as_ref().to_owned(), context)
+            .map_err(|()| location.new_custom_error(StyleParseErrorKind::UnspecifiedError))
+    }
+
+    /// Parses an image `url()`.
+    #[cfg(feature = "gecko")]
+    fn parse_url<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<SpecifiedImageUrl, ParseError<'i>> {
+        SpecifiedImageUrl::parse(context, input)
+    }
+
     /// Parses a `-moz-element(# <element-id>)`.
     fn parse_element<'i, 't>(input: &mut Parser<'i, 't>) -> Result<Atom, ParseError<'i>> {
         input.try(|i| i.expect_function_matching("-moz-element"))?;
@@ -252,24 +347,57 @@ impl Parse for Gradient {
             }
         }
 
-        let (kind, items) = input.parse_nested_block(|i| {
-            let shape = match shape {
+        let (kind, color_interpolation, items) = input.parse_nested_block(|i| {
+            let (shape, color_interpolation) = match shape {
                 Shape::Linear => GradientKind::parse_linear(context, i, &mut compat_mode)?,
                 Shape::Radial => GradientKind::parse_radial(context, i, &mut compat_mode)?,
             };
             let items = GradientItem::parse_comma_separated(context, i)?;
-            Ok((shape, items))
+            Ok((shape, color_interpolation, items))
         })?;
 
-        if items.len() < 2 {
-            return Err(input.new_custom_error(StyleParseErrorKind::UnspecifiedError));
-        }
-
         Ok(Gradient {
             items: items,
             repeating: repeating,
             kind: kind,
             compat_mode: compat_mode,
+            color_interpolation: color_interpolation,
+        })
+    }
+}
+
+impl Parse for ConicGradient {
+    fn parse<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i>> {
+        // FIXME: remove clone() when lifetimes are non-lexical
+        let func = input.expect_function()?.clone();
+        let repeating = match_ignore_ascii_case! { &func,
+            "conic-gradient" => false,
+            "repeating-conic-gradient" => true,
+            _ => return Err(input.new_custom_error(StyleParseErrorKind::UnexpectedFunction(func))),
+        };
+
+        input.parse_nested_block(|i| {
+            let angle = i.try(|i| {
+                i.expect_ident_matching("from")?;
+                Angle::parse_with_unitless(context, i)
+            }).ok();
+            let position = i.try(|i| {
+                i.expect_ident_matching("at")?;
+                Position::parse(context, i)
+            }).ok();
+            if angle.is_some() || position.is_some() {
+                i.expect_comma()?;
+            }
+            let items = generic::GradientItem::parse_comma_separated(context, i)?;
+            Ok(generic::ConicGradient {
+                angle,
+                position,
+                items,
+                repeating,
+            })
         })
     }
 }
@@ -536,6 +664,7 @@ impl Gradient {
             items: items,
             repeating: false,
             compat_mode: CompatMode::Modern,
+            color_interpolation: None,
         })
     }
 }
@@ -547,25 +676,46 @@ impl GradientKind {
         context: &ParserContext,
         input: &mut Parser<'i, 't>,
         compat_mode: &mut CompatMode,
-    ) -> Result<Self, ParseError<'i>> {
-        let direction = if let Ok(d) = input.try(|i| LineDirection::parse(context, i, compat_mode))
-        {
+    ) -> Result<(Self, Option<ColorInterpolationMethod>), ParseError<'i>> {
+        let mut color_interpolation = Self::parse_color_interpolation(context, input, *compat_mode);
+        let direction = input.try(|i| LineDirection::parse(context, i, compat_mode));
+        if color_interpolation.is_none() {
+            color_interpolation = Self::parse_color_interpolation(context, input, *compat_mode);
+        }
+        let direction = if let Ok(d) = direction {
             input.expect_comma()?;
             d
         } else {
+            if color_interpolation.is_some() {
+                input.expect_comma()?;
+            }
             match *compat_mode {
                 CompatMode::Modern => LineDirection::Vertical(Y::Bottom),
                 _ => LineDirection::Vertical(Y::Top),
             }
         };
-        Ok(generic::GradientKind::Linear(direction))
+        Ok((generic::GradientKind::Linear(direction), color_interpolation))
+    }
+
+    /// Parses an optional `<color-interpolation-method>`, which is only
+    /// allowed in unprefixed gradients.
+    fn parse_color_interpolation<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+        compat_mode: CompatMode,
+    ) -> Option<ColorInterpolationMethod> {
+        if compat_mode != CompatMode::Modern {
+            return None;
+        }
+        input.try(|i| ColorInterpolationMethod::parse(context, i)).ok()
     }
 
     fn parse_radial<'i, 't>(
         context: &ParserContext,
         input: &mut Parser<'i, 't>,
         compat_mode: &mut CompatMode,
-    ) -> Result<Self, ParseError<'i>> {
+    ) -> Result<(Self, Option<ColorInterpolationMethod>), ParseError<'i>> {
+        let mut color_interpolation = Self::parse_color_interpolation(context, input, *compat_mode);
         let (shape, position, angle, moz_position) = match *compat_mode {
             CompatMode::Modern => {
                 let shape = input.try(|i| EndingShape::parse(context, i, *compat_mode));
@@ -573,6 +723,10 @@ impl GradientKind {
                     i.expect_ident_matching("at")?;
                     Position::parse(context, i)
                 });
+                if color_interpolation.is_none() {
+                    color_interpolation =
+                        Self::parse_color_interpolation(context, input, *compat_mode);
+                }
                 (shape, position.ok(), None, None)
             },
             CompatMode::WebKit => {
@@ -613,7 +767,9 @@ impl GradientKind {
             },
         };
 
-        if shape.is_ok() || position.is_some() || angle.is_some() || moz_position.is_some() {
+        if shape.is_ok() || position.is_some() || angle.is_some() || moz_position.is_some() ||
+            color_interpolation.is_some()
+        {
             input.expect_comma()?;
         }
 
@@ -629,10 +785,13 @@ impl GradientKind {
                     *compat_mode = CompatMode::Modern;
                 }
                 let position = moz_position.unwrap_or(LegacyPosition::center());
-                return Ok(generic::GradientKind::Radial(
-                    shape,
-                    GradientPosition::Legacy(position),
-                    angle,
+                return Ok((
+                    generic::GradientKind::Radial(
+                        shape,
+                        GradientPosition::Legacy(position),
+                        angle,
+                    ),
+                    color_interpolation,
                 ));
             }
         }
@@ -640,15 +799,21 @@ impl GradientKind {
         let position = position.unwrap_or(Position::center());
         #[cfg(feature = "gecko")]
         {
-            return Ok(generic::GradientKind::Radial(
-                shape,
-                GradientPosition::Modern(position),
-                angle,
+            return Ok((
+                generic::GradientKind::Radial(
+                    shape,
+                    GradientPosition::Modern(position),
+                    angle,
+                ),
+                color_interpolation,
             ));
         }
         #[cfg(not(feature = "gecko"))]
         {
-            return Ok(generic::GradientKind::Radial(shape, position, angle));
+            return Ok((
+                generic::GradientKind::Radial(shape, position, angle),
+                color_interpolation,
+            ));
         }
     }
 }
@@ -928,37 +1093,168 @@ impl ShapeExtent {
     }
 }
 
-impl GradientItem {
+impl<P: Parse> generic::GradientItem<RGBAColor, P> {
     fn parse_comma_separated<'i, 't>(
         context: &ParserContext,
         input: &mut Parser<'i, 't>,
     ) -> Result<Vec<Self>, ParseError<'i>> {
+        let mut items = Vec::new();
         let mut seen_stop = false;
-        let items = input.parse_comma_separated(|input| {
-            if seen_stop {
-                if let Ok(hint) = input.try(|i| LengthOrPercentage::parse(context, i)) {
-                    seen_stop = false;
-                    return Ok(generic::GradientItem::InterpolationHint(hint));
+        // The number of color stops as authored, which doesn't count the
+        // second position of a stop.
+        let mut stop_count = 0;
+
+        loop {
+            input.parse_until_before(Delimiter::Comma, |input| {
+                if seen_stop {
+                    if let Ok(hint) = input.try(|i| P::parse(context, i)) {
+                        seen_stop = false;
+                        items.push(generic::GradientItem::InterpolationHint(hint));
+                        return Ok(());
+                    }
                 }
+
+                let stop = generic::ColorStop::<RGBAColor, P>::parse(context, input)?;
+
+                // A color stop with two positions is equivalent to two color
+                // stops with the same color.
+                if let Ok(multi_position) = input.try(|i| P::parse(context, i)) {
+                    let stop_color = stop.color.clone();
+                    items.push(generic::GradientItem::ColorStop(stop));
+                    items.push(generic::GradientItem::ColorStop(generic::ColorStop {
+                        color: stop_color,
+                        position: Some(multi_position),
+                    }));
+                } else {
+                    items.push(generic::GradientItem::ColorStop(stop));
+                }
+
+                seen_stop = true;
+                stop_count += 1;
+                Ok(())
+            })?;
+
+            match input.next() {
+                Err(_) => break,
+                Ok(&Token::Comma) => continue,
+                Ok(_) => unreachable!(),
             }
-            seen_stop = true;
-            ColorStop::parse(context, input).map(generic::GradientItem::ColorStop)
-        })?;
-        if !seen_stop || items.len() < 2 {
+        }
+
+        if !seen_stop {
             return Err(input.new_custom_error(StyleParseErrorKind::UnspecifiedError));
         }
+        if stop_count < 2 {
+            return Err(input.new_custom_error(ValueParseErrorKind::TooFewGradientStops));
+        }
         Ok(items)
     }
 }
 
-impl Parse for ColorStop {
+impl<P: Parse> Parse for generic::ColorStop<RGBAColor, P> {
     fn parse<'i, 't>(
         context: &ParserContext,
         input: &mut Parser<'i, 't>,
     ) -> Result<Self, ParseError<'i>> {
-        Ok(ColorStop {
+        Ok(generic::ColorStop {
             color: RGBAColor::parse(context, input)?,
-            position: input.try(|i| LengthOrPercentage::parse(context, i)).ok(),
+            position: input.try(|i| P::parse(context, i)).ok(),
+        })
+    }
+}
+
+impl Parse for CrossFade {
+    fn parse<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i>> {
+        input.expect_function_matching("cross-fade")?;
+        input.parse_nested_block(|i| {
+            let elements = i.parse_comma_separated(|i| CrossFadeElement::parse(context, i))?;
+            Ok(generic::CrossFade { elements })
+        })
+    }
+}
+
+impl Parse for CrossFadeElement {
+    fn parse<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i>> {
+        fn parse_percentage<'i, 't>(
+            context: &ParserContext,
+            input: &mut Parser<'i, 't>,
+        ) -> Result<Percentage, ParseError<'i>> {
+            let location = input.current_source_location();
+            let percentage = Percentage::parse_non_negative(context, input)?;
+            if percentage.get() > 1. {
+                return Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError));
+            }
+            Ok(percentage)
+        }
+
+        let mut percentage = input.try(|i| parse_percentage(context, i)).ok();
+        let image = Image::parse(context, input)?;
+        if percentage.is_none() {
+            percentage = input.try(|i| parse_percentage(context, i)).ok();
+        }
+        Ok(generic::CrossFadeElement { image, percentage })
+    }
+}
+
+impl Parse for ImageSet {
+    fn parse<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i>> {
+        input.expect_function_matching("image-set")?;
+        input.parse_nested_block(|i| {
+            let items = i.parse_comma_separated(|i| ImageSetItem::parse(context, i))?;
+            Ok(generic::ImageSet { items })
+        })
+    }
+}
+
+impl Parse for ImageSetItem {
+    fn parse<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i>> {
+        let image = Image::parse(context, input)?;
+        let resolution = input
+            .try(|i| Resolution::parse(context, i))
+            .unwrap_or(Resolution::X(1.));
+        let mime_type = input
+            .try(|i| -> Result<_, ParseError<'i>> {
+                i.expect_function_matching("type")?;
+                i.parse_nested_block(|i| Ok(i.expect_string()?.as_ref().to_owned()))
+            })
+            .ok();
+        Ok(generic::ImageSetItem {
+            image,
+            resolution,
+            mime_type,
+        })
+    }
+}
+
+impl Parse for ImageFunction {
+    fn parse<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i>> {
+        input.expect_function_matching("image")?;
+        input.parse_nested_block(|i| {
+            let url = i.try(|i| Image::parse_url(context, i)).ok();
+            let color = if url.is_some() {
+                i.try(|i| -> Result<_, ParseError<'i>> {
+                    i.expect_comma()?;
+                    RGBAColor::parse(context, i)
+                }).ok()
+            } else {
+                Some(RGBAColor::parse(context, i)?)
+            };
+            Ok(generic::ImageFunction { url, color })
         })
     }
 }
@@ -19,9 +19,11 @@ use super::{AllowQuirks, Number, Percentage, ToComputedValue};
 use values::{Auto, CSSFloat, Either, Normal};
 use values::computed::{self, CSSPixelLength, Context, ExtremumLength};
 use values::generics::NonNegative;
-use values::specified::calc::CalcNode;
+use values::generics::length::Size as GenericSize;
+use values::specified::calc::{CalcNode, MathFunction, MinMaxOp};
 
-pub use values::specified::calc::CalcLengthOrPercentage;
+pub use values::specified::calc::{CalcLengthOrPercentage, MinMaxLengthOrPercentage};
+pub use values::specified::calc::SteppedLength;
 pub use super::image::{ColorStop, EndingShape as GradientEndingShape, Gradient};
 pub use super::image::{GradientKind, Image};
 
@@ -49,6 +51,7 @@ pub fn au_to_int_px(au: f32) -> i32 {
 }
 
 /// A font relative length.
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Copy, Debug, MallocSizeOf, PartialEq, ToCss)]
 pub enum FontRelativeLength {
     /// A "em" value: https://drafts.csswg.org/css-values/#em
@@ -212,6 +215,7 @@ impl FontRelativeLength {
 /// A viewport-relative length.
 ///
 /// <https://drafts.csswg.org/css-values/#viewport-relative-lengths>
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Copy, Debug, MallocSizeOf, PartialEq, ToCss)]
 pub enum ViewportPercentageLength {
     /// A vw unit: https://drafts.csswg.org/css-values/#vw
@@ -251,6 +255,7 @@ impl ViewportPercentageLength {
 }
 
 /// HTML5 "character width", as defined in HTML5 § 14.5.4.
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Copy, Debug, MallocSizeOf, PartialEq, ToCss)]
 pub struct CharacterWidth(pub i32);
 
@@ -269,6 +274,7 @@ impl CharacterWidth {
 }
 
 /// Represents an absolute length with its unit
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Copy, Debug, MallocSizeOf, PartialEq, ToCss)]
 pub enum AbsoluteLength {
     /// An absolute length in pixels (px)
@@ -375,6 +381,7 @@ impl Add<AbsoluteLength> for AbsoluteLength {
 /// A `<length>` without taking `calc` expressions into account
 ///
 /// <https://drafts.csswg.org/css-values/#lengths>
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Copy, Debug, MallocSizeOf, PartialEq, ToCss)]
 pub enum NoCalcLength {
     /// An absolute length
@@ -414,6 +421,32 @@ impl Mul<CSSFloat> for NoCalcLength {
     }
 }
 
+impl From<Au> for NoCalcLength {
+    #[inline]
+    fn from(au: Au) -> Self {
+        NoCalcLength::from_px(au.to_f32_px())
+    }
+}
+
+/// Parses the unitless `<number>` component of a length or percentage as a
+/// length in pixels.
+///
+/// Only zero is accepted, unless the parsing mode allows unitless lengths or
+/// the quirk applies.
+#[inline]
+fn parse_length_or_percentage_component(
+    context: &ParserContext,
+    value: CSSFloat,
+    allow_quirks: AllowQuirks,
+) -> Result<NoCalcLength, ()> {
+    if value != 0. && !context.parsing_mode.allows_unitless_lengths() &&
+        !allow_quirks.allowed(context.quirks_mode)
+    {
+        return Err(());
+    }
+    Ok(NoCalcLength::from_px(value))
+}
+
 impl NoCalcLength {
     /// Parse a given absolute or relative dimension.
     pub fn parse_dimension(
@@ -478,6 +511,28 @@ impl NoCalcLength {
         }
     }
 
+    /// Whether this length depends on font metrics, and thus needs to be
+    /// recomputed when the font changes.
+    #[inline]
+    pub fn is_font_relative(&self) -> bool {
+        match *self {
+            NoCalcLength::FontRelative(..) | NoCalcLength::ServoCharacterWidth(..) => true,
+            NoCalcLength::Absolute(..) | NoCalcLength::ViewportPercentage(..) => false,
+        }
+    }
+
+    /// Whether this length depends on the viewport size, and thus needs to be
+    /// recomputed when the viewport is resized.
+    #[inline]
+    pub fn is_viewport_relative(&self) -> bool {
+        match *self {
+            NoCalcLength::ViewportPercentage(..) => true,
+            NoCalcLength::Absolute(..) |
+            NoCalcLength::FontRelative(..) |
+            NoCalcLength::ServoCharacterWidth(..) => false,
+        }
+    }
+
     /// Get a px value without context.
     #[inline]
     pub fn to_computed_pixel_length_without_context(&self) -> Result<CSSFloat, ()> {
@@ -500,6 +555,7 @@ impl SpecifiedValueInfo for NoCalcLength {}
 /// This is commonly used for the `<length>` values.
 ///
 /// <https://drafts.csswg.org/css-values/#lengths>
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Debug, MallocSizeOf, PartialEq, SpecifiedValueInfo, ToCss)]
 pub enum Length {
     /// The internal length type that cannot parse `calc`
@@ -508,6 +564,14 @@ pub enum Length {
     ///
     /// <https://drafts.csswg.org/css-values/#calc-notation>
     Calc(Box<CalcLengthOrPercentage>),
+    /// A `min()`, `max()` or `clamp()` expression.
+    ///
+    /// <https://drafts.csswg.org/css-values-4/#comp-func>
+    MinMax(Box<MinMaxLengthOrPercentage>),
+    /// A `round()`, `mod()` or `rem()` expression.
+    ///
+    /// <https://drafts.csswg.org/css-values-4/#round-func>
+    Stepped(Box<SteppedLength>),
 }
 
 impl From<NoCalcLength> for Length {
@@ -517,6 +581,13 @@ impl From<NoCalcLength> for Length {
     }
 }
 
+impl From<Au> for Length {
+    #[inline]
+    fn from(au: Au) -> Self {
+        Length::NoCalc(au.into())
+    }
+}
+
 impl Mul<CSSFloat> for Length {
     type Output = Length;
 
@@ -525,6 +596,8 @@ impl Mul<CSSFloat> for Length {
         match self {
             Length::NoCalc(inner) => Length::NoCalc(inner * scalar),
             Length::Calc(..) => panic!("Can't multiply Calc!"),
+            Length::MinMax(..) => panic!("Can't multiply MinMax!"),
+            Length::Stepped(..) => panic!("Can't multiply Stepped!"),
         }
     }
 }
@@ -564,6 +637,46 @@ impl Length {
         Length::NoCalc(NoCalcLength::zero())
     }
 
+    #[inline]
+    /// Checks whether the length value is an absolute zero, like `0` or
+    /// `0px`.
+    ///
+    /// Relative lengths and `calc()` expressions are never considered zero,
+    /// matching `NoCalcLength::is_zero`.
+    pub fn is_zero(&self) -> bool {
+        match *self {
+            Length::NoCalc(ref l) => l.is_zero(),
+            Length::Calc(..) | Length::MinMax(..) | Length::Stepped(..) => false,
+        }
+    }
+
+    /// Whether this length depends on font metrics (`em`, `ex`, `ch` or `rem`
+    /// units), and thus needs to be recomputed when the font changes.
+    pub fn is_font_relative(&self) -> bool {
+        match *self {
+            Length::NoCalc(ref l) => l.is_font_relative(),
+            Length::Calc(ref calc) => calc.is_font_relative(),
+            Length::MinMax(ref min_max) => min_max.args.iter().any(|arg| arg.is_font_relative()),
+            Length::Stepped(ref stepped) => {
+                stepped.value.is_font_relative() || stepped.step.is_font_relative()
+            },
+        }
+    }
+
+    /// Whether this length depends on the viewport size (`vw`, `vh`, `vmin`
+    /// or `vmax` units), and thus needs to be recomputed when the viewport is
+    /// resized.
+    pub fn is_viewport_relative(&self) -> bool {
+        match *self {
+            Length::NoCalc(ref l) => l.is_viewport_relative(),
+            Length::Calc(ref calc) => calc.is_viewport_relative(),
+            Length::MinMax(ref min_max) => min_max.args.iter().any(|arg| arg.is_viewport_relative()),
+            Length::Stepped(ref stepped) => {
+                stepped.value.is_viewport_relative() || stepped.step.is_viewport_relative()
+            },
+        }
+    }
+
     #[inline]
     fn parse_internal<'i, 't>(
         context: &ParserContext,
@@ -572,7 +685,7 @@ impl Length {
         allow_quirks: AllowQuirks,
     ) -> Result<Self, ParseError<'i>> {
         // FIXME: remove early returns when lifetimes are non-lexical
-        {
+        let function = {
             let location = input.current_source_location();
             let token = input.next()?;
             match *token {
@@ -585,22 +698,30 @@ impl Length {
                         .map_err(|()| location.new_unexpected_token_error(token.clone()))
                 },
                 Token::Number { value, .. } if num_context.is_ok(context.parsing_mode, value) => {
-                    if value != 0. && !context.parsing_mode.allows_unitless_lengths() &&
-                        !allow_quirks.allowed(context.quirks_mode)
-                    {
-                        return Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError));
-                    }
-                    return Ok(Length::NoCalc(NoCalcLength::Absolute(AbsoluteLength::Px(
-                        value,
-                    ))));
+                    return parse_length_or_percentage_component(context, value, allow_quirks)
+                        .map(Length::NoCalc)
+                        .map_err(|()| {
+                            location.new_custom_error(StyleParseErrorKind::UnspecifiedError)
+                        })
+                },
+                Token::Function(ref name) => match MathFunction::from_function_name(name) {
+                    Ok(function) => function,
+                    Err(()) => return Err(location.new_unexpected_token_error(token.clone())),
                 },
-                Token::Function(ref name) if name.eq_ignore_ascii_case("calc") => {},
                 ref token => return Err(location.new_unexpected_token_error(token.clone())),
             }
-        }
-        input.parse_nested_block(|input| {
-            CalcNode::parse_length(context, input, num_context)
-                .map(|calc| Length::Calc(Box::new(calc)))
+        };
+        input.parse_nested_block(|input| match function {
+            MathFunction::Calc => CalcNode::parse_length(context, input, num_context)
+                .map(|calc| Length::Calc(Box::new(calc))),
+            MathFunction::MinMax(op) => {
+                CalcNode::parse_length_min_max(context, input, op, num_context)
+                    .map(|min_max| Length::MinMax(Box::new(min_max)))
+            },
+            MathFunction::Stepped(op) => {
+                CalcNode::parse_length_stepped(context, input, op, num_context)
+                    .map(|stepped| Length::Stepped(Box::new(stepped)))
+            },
         })
     }
 
@@ -633,6 +754,23 @@ impl Length {
     pub fn from_px(px_value: CSSFloat) -> Length {
         Length::NoCalc(NoCalcLength::from_px(px_value))
     }
+
+    /// Get an absolute length from an integer px value, as found in legacy
+    /// presentational attributes like `width="100"`.
+    #[inline]
+    pub fn from_px_i32(px_value: i32) -> Length {
+        Length::from_px(px_value as CSSFloat)
+    }
+
+    /// Compares two lengths once resolved against `context`.
+    ///
+    /// Lengths in different units (e.g. `1em` and `10px`) can't be ordered
+    /// without a context, so both sides are computed to app units first.
+    pub fn cmp_computed(&self, other: &Self, context: &Context) -> cmp::Ordering {
+        let this = Au::from(self.to_computed_value(context));
+        let other = Au::from(other.to_computed_value(context));
+        this.cmp(&other)
+    }
 }
 
 impl Parse for Length {
@@ -701,11 +839,14 @@ pub type NonNegativeLengthOrAuto = Either<NonNegativeLength, Auto>;
 
 /// A length or a percentage value.
 #[allow(missing_docs)]
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Debug, MallocSizeOf, PartialEq, SpecifiedValueInfo, ToCss)]
 pub enum LengthOrPercentage {
     Length(NoCalcLength),
     Percentage(computed::Percentage),
     Calc(Box<CalcLengthOrPercentage>),
+    MinMax(Box<MinMaxLengthOrPercentage>),
+    Stepped(Box<SteppedLength>),
 }
 
 impl From<Length> for LengthOrPercentage {
@@ -713,6 +854,8 @@ impl From<Length> for LengthOrPercentage {
         match len {
             Length::NoCalc(l) => LengthOrPercentage::Length(l),
             Length::Calc(l) => LengthOrPercentage::Calc(l),
+            Length::MinMax(l) => LengthOrPercentage::MinMax(l),
+            Length::Stepped(l) => LengthOrPercentage::Stepped(l),
         }
     }
 }
@@ -759,7 +902,7 @@ impl LengthOrPercentage {
         allow_quirks: AllowQuirks,
     ) -> Result<Self, ParseError<'i>> {
         // FIXME: remove early returns when lifetimes are non-lexical
-        {
+        let function = {
             let location = input.current_source_location();
             let token = input.next()?;
             match *token {
@@ -779,22 +922,38 @@ impl LengthOrPercentage {
                     )))
                 },
                 Token::Number { value, .. } if num_context.is_ok(context.parsing_mode, value) => {
-                    if value != 0. && !context.parsing_mode.allows_unitless_lengths() &&
-                        !allow_quirks.allowed(context.quirks_mode)
-                    {
-                        return Err(location.new_unexpected_token_error(token.clone()));
-                    } else {
-                        return Ok(LengthOrPercentage::Length(NoCalcLength::from_px(value)));
-                    }
+                    return parse_length_or_percentage_component(context, value, allow_quirks)
+                        .map(LengthOrPercentage::Length)
+                        .map_err(|()| location.new_unexpected_token_error(token.clone()))
+                },
+                Token::Function(ref name) => match MathFunction::from_function_name(name) {
+                    Ok(function) => function,
+                    Err(()) => return Err(location.new_unexpected_token_error(token.clone())),
                 },
-                Token::Function(ref name) if name.eq_ignore_ascii_case("calc") => {},
                 _ => return Err(location.new_unexpected_token_error(token.clone())),
             }
-        }
+        };
 
-        let calc = input
-            .parse_nested_block(|i| CalcNode::parse_length_or_percentage(context, i, num_context))?;
-        Ok(LengthOrPercentage::Calc(Box::new(calc)))
+        match function {
+            MathFunction::Calc => {
+                let calc = input.parse_nested_block(|i| {
+                    CalcNode::parse_length_or_percentage(context, i, num_context)
+                })?;
+                Ok(LengthOrPercentage::Calc(Box::new(calc)))
+            },
+            MathFunction::MinMax(op) => {
+                let min_max = input.parse_nested_block(|i| {
+                    CalcNode::parse_length_or_percentage_min_max(context, i, op, num_context)
+                })?;
+                Ok(LengthOrPercentage::MinMax(Box::new(min_max)))
+            },
+            MathFunction::Stepped(op) => {
+                let stepped = input.parse_nested_block(|i| {
+                    CalcNode::parse_length_stepped(context, i, op, num_context)
+                })?;
+                Ok(LengthOrPercentage::Stepped(Box::new(stepped)))
+            },
+        }
     }
 
     /// Parse a non-negative length.
@@ -847,12 +1006,14 @@ impl LengthOrPercentage {
 
 /// Either a `<length>`, a `<percentage>`, or the `auto` keyword.
 #[allow(missing_docs)]
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Debug, MallocSizeOf, PartialEq, SpecifiedValueInfo, ToCss)]
 pub enum LengthOrPercentageOrAuto {
     Length(NoCalcLength),
     Percentage(computed::Percentage),
     Auto,
     Calc(Box<CalcLengthOrPercentage>),
+    MinMax(Box<MinMaxLengthOrPercentage>),
 }
 
 impl From<NoCalcLength> for LengthOrPercentageOrAuto {
@@ -877,7 +1038,7 @@ impl LengthOrPercentageOrAuto {
         allow_quirks: AllowQuirks,
     ) -> Result<Self, ParseError<'i>> {
         // FIXME: remove early returns when lifetimes are non-lexical
-        {
+        let min_max = {
             let location = input.current_source_location();
             let token = input.next()?;
             match *token {
@@ -897,21 +1058,29 @@ impl LengthOrPercentageOrAuto {
                     )))
                 },
                 Token::Number { value, .. } if num_context.is_ok(context.parsing_mode, value) => {
-                    if value != 0. && !context.parsing_mode.allows_unitless_lengths() &&
-                        !allow_quirks.allowed(context.quirks_mode)
-                    {
-                        return Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError));
-                    }
-                    return Ok(LengthOrPercentageOrAuto::Length(NoCalcLength::Absolute(
-                        AbsoluteLength::Px(value),
-                    )));
+                    return parse_length_or_percentage_component(context, value, allow_quirks)
+                        .map(LengthOrPercentageOrAuto::Length)
+                        .map_err(|()| {
+                            location.new_custom_error(StyleParseErrorKind::UnspecifiedError)
+                        })
                 },
                 Token::Ident(ref value) if value.eq_ignore_ascii_case("auto") => {
                     return Ok(LengthOrPercentageOrAuto::Auto)
                 },
-                Token::Function(ref name) if name.eq_ignore_ascii_case("calc") => {},
+                Token::Function(ref name) if name.eq_ignore_ascii_case("calc") => None,
+                Token::Function(ref name) => match MinMaxOp::from_function_name(name) {
+                    Ok(op) => Some(op),
+                    Err(()) => return Err(location.new_unexpected_token_error(token.clone())),
+                },
                 _ => return Err(location.new_unexpected_token_error(token.clone())),
             }
+        };
+
+        if let Some(op) = min_max {
+            let min_max = input.parse_nested_block(|i| {
+                CalcNode::parse_length_or_percentage_min_max(context, i, op, num_context)
+            })?;
+            return Ok(LengthOrPercentageOrAuto::MinMax(Box::new(min_max)));
         }
 
         let calc = input
@@ -1017,12 +1186,14 @@ impl Parse for NonNegativeLengthOrPercentageOrAuto {
 }
 
 /// Either a `<length>`, a `<percentage>`, or the `none` keyword.
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Debug, MallocSizeOf, PartialEq, SpecifiedValueInfo, ToCss)]
 #[allow(missing_docs)]
 pub enum LengthOrPercentageOrNone {
     Length(NoCalcLength),
     Percentage(computed::Percentage),
     Calc(Box<CalcLengthOrPercentage>),
+    MinMax(Box<MinMaxLengthOrPercentage>),
     None,
 }
 
@@ -1034,7 +1205,7 @@ impl LengthOrPercentageOrNone {
         allow_quirks: AllowQuirks,
     ) -> Result<Self, ParseError<'i>> {
         // FIXME: remove early returns when lifetimes are non-lexical
-        {
+        let min_max = {
             let location = input.current_source_location();
             let token = input.next()?;
             match *token {
@@ -1054,21 +1225,29 @@ impl LengthOrPercentageOrNone {
                     )))
                 },
                 Token::Number { value, .. } if num_context.is_ok(context.parsing_mode, value) => {
-                    if value != 0. && !context.parsing_mode.allows_unitless_lengths() &&
-                        !allow_quirks.allowed(context.quirks_mode)
-                    {
-                        return Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError));
-                    }
-                    return Ok(LengthOrPercentageOrNone::Length(NoCalcLength::Absolute(
-                        AbsoluteLength::Px(value),
-                    )));
+                    return parse_length_or_percentage_component(context, value, allow_quirks)
+                        .map(LengthOrPercentageOrNone::Length)
+                        .map_err(|()| {
+                            location.new_custom_error(StyleParseErrorKind::UnspecifiedError)
+                        })
+                },
+                Token::Function(ref name) if name.eq_ignore_ascii_case("calc") => None,
+                Token::Function(ref name) => match MinMaxOp::from_function_name(name) {
+                    Ok(op) => Some(op),
+                    Err(()) => return Err(location.new_unexpected_token_error(token.clone())),
                 },
-                Token::Function(ref name) if name.eq_ignore_ascii_case("calc") => {},
                 Token::Ident(ref value) if value.eq_ignore_ascii_case("none") => {
                     return Ok(LengthOrPercentageOrNone::None)
                 },
                 _ => return Err(location.new_unexpected_token_error(token.clone())),
             }
+        };
+
+        if let Some(op) = min_max {
+            let min_max = input.parse_nested_block(|i| {
+                CalcNode::parse_length_or_percentage_min_max(context, i, op, num_context)
+            })?;
+            return Ok(LengthOrPercentageOrNone::MinMax(Box::new(min_max)));
         }
 
         let calc = input
@@ -1165,6 +1344,11 @@ pub type LengthOrNormal = Either<Length, Normal>;
 pub type LengthOrAuto = Either<Length, Auto>;
 
 /// Either a `<length>` or a `<number>`.
+///
+/// A bare `0` is valid as both. The generic `Parse` implementation tries
+/// `<length>` first, so it yields a zero length, while `parse_non_negative`
+/// (used by the properties that actually accept this type) prefers
+/// `<number>`, as browsers do. Any other unitless value is always a number.
 pub type LengthOrNumber = Either<Length, Number>;
 
 impl LengthOrNumber {
@@ -1252,6 +1436,52 @@ impl MozLength {
     }
 }
 
+/// A specified value for the `width` or `height` properties, with support
+/// for the intrinsic sizing keywords.
+pub type Size = GenericSize<LengthOrPercentageOrAuto, LengthOrPercentage>;
+
+impl Parse for Size {
+    fn parse<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i>> {
+        Size::parse_quirky(context, input, AllowQuirks::No)
+    }
+}
+
+impl Size {
+    /// Parses, with quirks.
+    pub fn parse_quirky<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+        allow_quirks: AllowQuirks,
+    ) -> Result<Self, ParseError<'i>> {
+        if let Ok(length) = input.try(|i| {
+            LengthOrPercentageOrAuto::parse_non_negative_quirky(context, i, allow_quirks)
+        }) {
+            return Ok(GenericSize::LengthOrPercentageOrAuto(length));
+        }
+
+        if input.try(|i| i.expect_function_matching("fit-content")).is_ok() {
+            return input.parse_nested_block(|i| {
+                let length = LengthOrPercentage::parse_non_negative(context, i)?;
+                Ok(GenericSize::FitContent(length))
+            });
+        }
+
+        try_match_ident_ignore_ascii_case! { input,
+            "min-content" => Ok(GenericSize::MinContent),
+            "max-content" => Ok(GenericSize::MaxContent),
+        }
+    }
+
+    /// Returns `auto`.
+    #[inline]
+    pub fn auto() -> Self {
+        GenericSize::LengthOrPercentageOrAuto(LengthOrPercentageOrAuto::auto())
+    }
+}
+
 /// A value suitable for a `max-width` or `max-height` property.
 #[allow(missing_docs)]
 #[derive(Clone, Debug, MallocSizeOf, PartialEq, SpecifiedValueInfo, ToCss)]
@@ -38,13 +38,14 @@ pub use self::font::{FontSize, FontSizeAdjust, FontStretch, FontSynthesis, FontV
 pub use self::font::{FontFamily, FontLanguageOverride, FontStyle, FontVariantEastAsian, FontVariationSettings};
 pub use self::font::{FontFeatureSettings, FontVariantLigatures, FontVariantNumeric};
 pub use self::font::{MozScriptLevel, MozScriptMinSize, MozScriptSizeMultiplier, XLang, XTextZoom};
-pub use self::box_::{AnimationIterationCount, AnimationName, Contain, Display};
-pub use self::box_::{OverflowClipBox, OverscrollBehavior, Perspective};
+pub use self::box_::{AnimationIterationCount, AnimationName, AspectRatio, Contain, Display};
+pub use self::box_::{OverflowClipBox, OverscrollBehavior, Perspective, Ratio};
 pub use self::box_::{ScrollSnapType, TouchAction, VerticalAlign, WillChange};
-pub use self::color::{Color, ColorPropertyValue, RGBAColor};
+pub use self::color::{Color, ColorInterpolationMethod, ColorPropertyValue, RGBAColor};
 pub use self::counters::{Content, ContentItem, CounterIncrement, CounterReset};
 pub use self::effects::{BoxShadow, Filter, SimpleShadow};
 pub use self::flex::FlexBasis;
+pub use self::frequency::Frequency;
 #[cfg(feature = "gecko")]
 pub use self::gecko::ScrollSnapPoint;
 pub use self::image::{ColorStop, EndingShape as GradientEndingShape, Gradient};
@@ -54,7 +55,8 @@ pub use self::length::{AbsoluteLength, CalcLengthOrPercentage, CharacterWidth};
 pub use self::length::{FontRelativeLength, Length, LengthOrNumber};
 pub use self::length::{LengthOrPercentage, LengthOrPercentageOrAuto};
 pub use self::length::{LengthOrPercentageOrNone, MaxLength, MozLength};
-pub use self::length::{NoCalcLength, ViewportPercentageLength};
+pub use self::length::{MinMaxLengthOrPercentage, SteppedLength};
+pub use self::length::{NoCalcLength, Size, ViewportPercentageLength};
 pub use self::length::{NonNegativeLengthOrPercentage, NonNegativeLengthOrPercentageOrAuto};
 pub use self::list::Quotes;
 #[cfg(feature = "gecko")]
@@ -95,6 +97,7 @@ pub mod counters;
 pub mod effects;
 pub mod flex;
 pub mod font;
+pub mod frequency;
 #[cfg(feature = "gecko")]
 pub mod gecko;
 pub mod grid;
@@ -174,6 +177,7 @@ impl BorderStyle {
 /// A CSS `<number>` specified value.
 ///
 /// https://drafts.csswg.org/css-values-3/#number-value
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Copy, Debug, MallocSizeOf, PartialEq, PartialOrd)]
 pub struct Number {
     /// The numeric value itself.
@@ -328,6 +332,7 @@ impl Parse for GreaterThanOrEqualToOneNumber {
 ///
 /// FIXME(emilio): Should probably use Either.
 #[allow(missing_docs)]
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Copy, Debug, MallocSizeOf, PartialEq, SpecifiedValueInfo,
          ToCss)]
 pub enum NumberOrPercentage {
@@ -372,11 +377,26 @@ impl Parse for NumberOrPercentage {
 pub struct Opacity(Number);
 
 impl Parse for Opacity {
+    /// Opacity accepts `<number> | <percentage>`, so a percentage is parsed
+    /// and then converted into its number form, which is how it serializes.
+    ///
+    /// <https://drafts.csswg.org/css-color/#transparency>
     fn parse<'i, 't>(
         context: &ParserContext,
         input: &mut Parser<'i, 't>,
     ) -> Result<Self, ParseError<'i>> {
-        Number::parse(context, input).map(Opacity)
+        let number = match NumberOrPercentage::parse(context, input)? {
+            NumberOrPercentage::Percentage(p) => Number {
+                value: p.get(),
+                calc_clamping_mode: if p.is_calc() {
+                    Some(AllowedNumericType::All)
+                } else {
+                    None
+                },
+            },
+            NumberOrPercentage::Number(n) => n,
+        };
+        Ok(Opacity(number))
     }
 }
 
@@ -758,6 +778,7 @@ impl AllowQuirks {
 /// An attr(...) rule
 ///
 /// `[namespace? `|`]? ident`
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Debug, Eq, MallocSizeOf, PartialEq, SpecifiedValueInfo,
          ToComputedValue)]
 #[css(function)]
@@ -839,19 +860,28 @@ impl Attr {
             Err(input.new_custom_error(StyleParseErrorKind::UnspecifiedError))
         }
     }
-}
 
-impl ToCss for Attr {
-    fn to_css<W>(&self, dest: &mut CssWriter<W>) -> fmt::Result
+    /// Serializes the possibly namespaced attribute name, without the
+    /// surrounding `attr()`.
+    pub fn name_to_css<W>(&self, dest: &mut CssWriter<W>) -> fmt::Result
     where
         W: Write,
     {
-        dest.write_str("attr(")?;
         if let Some((ref prefix, ref _url)) = self.namespace {
             serialize_atom_identifier(prefix, dest)?;
             dest.write_str("|")?;
         }
-        serialize_atom_identifier(&self.attribute, dest)?;
+        serialize_atom_identifier(&self.attribute, dest)
+    }
+}
+
+impl ToCss for Attr {
+    fn to_css<W>(&self, dest: &mut CssWriter<W>) -> fmt::Result
+    where
+        W: Write,
+    {
+        dest.write_str("attr(")?;
+        self.name_to_css(dest)?;
         dest.write_str(")")
     }
 }
@@ -15,6 +15,7 @@ use values::computed::percentage::Percentage as ComputedPercentage;
 use values::specified::calc::CalcNode;
 
 /// A percentage value.
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Copy, Debug, Default, MallocSizeOf, PartialEq)]
 pub struct Percentage {
     /// The percentage value as a float.
@@ -16,6 +16,7 @@ use std::fmt::{self, Write};
 use std::ops::Range;
 use str::HTML_SPACE_CHARACTERS;
 use style_traits::{CssWriter, ParseError, StyleParseErrorKind, ToCss};
+use style_traits::values::specified::AllowedNumericType;
 use values::{Either, None_};
 use values::computed::{CalcLengthOrPercentage, LengthOrPercentage as ComputedLengthOrPercentage};
 use values::computed::{Context, Percentage, ToComputedValue};
@@ -34,6 +35,7 @@ pub type HorizontalPosition = PositionComponent<X>;
 pub type VerticalPosition = PositionComponent<Y>;
 
 /// The specified value of a component of a CSS `<position>`.
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Debug, MallocSizeOf, PartialEq, SpecifiedValueInfo, ToCss)]
 pub enum PositionComponent<S> {
     /// `center`
@@ -45,6 +47,7 @@ pub enum PositionComponent<S> {
 }
 
 /// A keyword for the X direction.
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Copy, Debug, Eq, Hash, MallocSizeOf, Parse, PartialEq,
          SpecifiedValueInfo, ToComputedValue, ToCss)]
 #[allow(missing_docs)]
@@ -54,6 +57,7 @@ pub enum X {
 }
 
 /// A keyword for the Y direction.
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[derive(Clone, Copy, Debug, Eq, Hash, MallocSizeOf, Parse, PartialEq,
          SpecifiedValueInfo, ToComputedValue, ToCss)]
 #[allow(missing_docs)]
@@ -204,16 +208,39 @@ impl<S: Parse> PositionComponent<S> {
         input: &mut Parser<'i, 't>,
         allow_quirks: AllowQuirks,
     ) -> Result<Self, ParseError<'i>> {
+        Self::parse_internal(context, input, AllowedNumericType::All, allow_quirks)
+    }
+
+    /// Parses a component of a CSS position, rejecting negative lengths and
+    /// percentages.
+    pub fn parse_non_negative<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i>> {
+        Self::parse_internal(context, input, AllowedNumericType::NonNegative, AllowQuirks::No)
+    }
+
+    fn parse_internal<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+        num_context: AllowedNumericType,
+        allow_quirks: AllowQuirks,
+    ) -> Result<Self, ParseError<'i>> {
+        let parse_length_or_percentage = |i: &mut Parser<'i, 't>| match num_context {
+            AllowedNumericType::NonNegative => {
+                LengthOrPercentage::parse_non_negative_quirky(context, i, allow_quirks)
+            },
+            _ => LengthOrPercentage::parse_quirky(context, i, allow_quirks),
+        };
+
         if input.try(|i| i.expect_ident_matching("center")).is_ok() {
             return Ok(PositionComponent::Center);
         }
-        if let Ok(lop) = input.try(|i| LengthOrPercentage::parse_quirky(context, i, allow_quirks)) {
+        if let Ok(lop) = input.try(&parse_length_or_percentage) {
             return Ok(PositionComponent::Length(lop));
         }
         let keyword = S::parse(context, input)?;
-        let lop = input
-            .try(|i| LengthOrPercentage::parse_quirky(context, i, allow_quirks))
-            .ok();
+        let lop = input.try(&parse_length_or_percentage).ok();
         Ok(PositionComponent::Side(keyword, lop))
     }
 }
@@ -300,6 +327,28 @@ impl Side for Y {
     }
 }
 
+impl X {
+    /// Returns the opposite horizontal side.
+    #[inline]
+    pub fn opposite(self) -> Self {
+        match self {
+            X::Left => X::Right,
+            X::Right => X::Left,
+        }
+    }
+}
+
+impl Y {
+    /// Returns the opposite vertical side.
+    #[inline]
+    pub fn opposite(self) -> Self {
+        match self {
+            Y::Top => Y::Bottom,
+            Y::Bottom => Y::Top,
+        }
+    }
+}
+
 /// The specified value of a legacy CSS `<position>`
 /// Modern position syntax supports 3 and 4-value syntax. That means:
 /// If three or four values are given, then each <percentage> or <length> represents an offset
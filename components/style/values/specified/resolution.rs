@@ -12,7 +12,8 @@ use style_traits::{ParseError, StyleParseErrorKind};
 use values::CSSFloat;
 
 /// A specified resolution.
-#[derive(Clone, Debug, PartialEq, ToCss)]
+#[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
+#[derive(Clone, Copy, Debug, MallocSizeOf, PartialEq, SpecifiedValueInfo, ToCss)]
 pub enum Resolution {
     /// Dots per inch.
     #[css(dimension)]
@@ -104,7 +104,7 @@ impl ToComputedValue for LineHeight {
 
     #[inline]
     fn to_computed_value(&self, context: &Context) -> Self::ComputedValue {
-        use values::computed::Length as ComputedLength;
+        use values::computed::CalcLengthOrPercentage as ComputedCalcLengthOrPercentage;
         use values::specified::length::FontBaseSize;
         match *self {
             GenericLineHeight::Normal => GenericLineHeight::Normal,
@@ -114,6 +114,12 @@ impl ToComputedValue for LineHeight {
                 GenericLineHeight::Number(number.to_computed_value(context))
             },
             GenericLineHeight::Length(ref non_negative_lop) => {
+                // Percentages are relative to the font size.
+                let resolve_calc = |computed_calc: ComputedCalcLengthOrPercentage| {
+                    let font_size = FontRelativeLength::Em(1.)
+                        .to_computed_value(context, FontBaseSize::CurrentStyle);
+                    computed_calc.resolve_against_length(font_size)
+                };
                 let result = match non_negative_lop.0 {
                     LengthOrPercentage::Length(NoCalcLength::Absolute(ref abs)) => {
                         context
@@ -123,20 +129,15 @@ impl ToComputedValue for LineHeight {
                     LengthOrPercentage::Length(ref length) => length.to_computed_value(context),
                     LengthOrPercentage::Percentage(ref p) => FontRelativeLength::Em(p.0)
                         .to_computed_value(context, FontBaseSize::CurrentStyle),
-                    LengthOrPercentage::Calc(ref calc) => {
-                        let computed_calc =
-                            calc.to_computed_value_zoomed(context, FontBaseSize::CurrentStyle);
-                        let font_relative_length =
-                            FontRelativeLength::Em(computed_calc.percentage())
-                                .to_computed_value(context, FontBaseSize::CurrentStyle)
-                                .px();
-
-                        let absolute_length = computed_calc.unclamped_length().px();
-                        let pixel = computed_calc
-                            .clamping_mode
-                            .clamp(absolute_length + font_relative_length);
-                        ComputedLength::new(pixel)
-                    },
+                    LengthOrPercentage::Calc(ref calc) => resolve_calc(
+                        calc.to_computed_value_zoomed(context, FontBaseSize::CurrentStyle),
+                    ),
+                    LengthOrPercentage::MinMax(ref min_max) => resolve_calc(
+                        min_max.to_computed_value_zoomed(context, FontBaseSize::CurrentStyle),
+                    ),
+                    LengthOrPercentage::Stepped(ref stepped) => resolve_calc(
+                        stepped.to_computed_value_zoomed(context, FontBaseSize::CurrentStyle),
+                    ),
                 };
                 GenericLineHeight::Length(result.into())
             },
@@ -0,0 +1,68 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Conversions between app units and the absolute CSS units, for embedders
+//! doing their own layout math.
+//!
+//! These use the same ratios as absolute lengths do when they are parsed and
+//! computed.
+
+use app_units::{Au, MAX_AU, MIN_AU};
+
+pub use values::specified::length::{AU_PER_CM, AU_PER_IN, AU_PER_MM, AU_PER_PC};
+pub use values::specified::length::{AU_PER_PT, AU_PER_PX, AU_PER_Q};
+
+/// Rounds `au` to the nearest app unit, saturating to the range of app units.
+#[inline]
+fn to_au(au: f64) -> Au {
+    Au(au.round().min(MAX_AU.0 as f64).max(MIN_AU.0 as f64) as i32)
+}
+
+/// Converts pixels to app units.
+#[inline]
+pub fn px_to_au(px: f64) -> Au {
+    to_au(px * AU_PER_PX as f64)
+}
+
+/// Converts app units to pixels.
+#[inline]
+pub fn au_to_px(au: Au) -> f64 {
+    au.0 as f64 / AU_PER_PX as f64
+}
+
+/// Converts inches to app units.
+#[inline]
+pub fn in_to_au(inches: f64) -> Au {
+    to_au(inches * AU_PER_IN as f64)
+}
+
+/// Converts centimeters to app units.
+#[inline]
+pub fn cm_to_au(cm: f64) -> Au {
+    to_au(cm * AU_PER_CM as f64)
+}
+
+/// Converts millimeters to app units.
+#[inline]
+pub fn mm_to_au(mm: f64) -> Au {
+    to_au(mm * AU_PER_MM as f64)
+}
+
+/// Converts quarter-millimeters to app units.
+#[inline]
+pub fn q_to_au(q: f64) -> Au {
+    to_au(q * AU_PER_Q as f64)
+}
+
+/// Converts points to app units.
+#[inline]
+pub fn pt_to_au(pt: f64) -> Au {
+    to_au(pt * AU_PER_PT as f64)
+}
+
+/// Converts picas to app units.
+#[inline]
+pub fn pc_to_au(pc: f64) -> Au {
+    to_au(pc * AU_PER_PC as f64)
+}
@@ -173,6 +173,8 @@ pub enum ValueParseErrorKind<'i> {
     InvalidColor(Token<'i>),
     /// An invalid filter value was encountered.
     InvalidFilter(Token<'i>),
+    /// A gradient had fewer than two color stops.
+    TooFewGradientStops,
 }
 
 impl<'i> StyleParseErrorKind<'i> {
@@ -187,6 +189,9 @@ impl<'i> StyleParseErrorKind<'i> {
                     ValueParseErrorKind::InvalidFilter(token) => {
                         StyleParseErrorKind::InvalidFilter(name, token)
                     }
+                    ValueParseErrorKind::TooFewGradientStops => {
+                        StyleParseErrorKind::OtherInvalidValue(name)
+                    }
                 }
             }
             _ => StyleParseErrorKind::OtherInvalidValue(name),
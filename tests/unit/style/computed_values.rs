@@ -0,0 +1,1193 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Tests for computing specified values against a style context.
+
+use app_units::{Au, MAX_AU, MIN_AU};
+use cssparser::RGBA;
+use euclid::{Size2D, TypedScale, TypedSize2D};
+use parsing::{parse_value, with_parser_context};
+use style::Atom;
+use style::context::{QuirksMode, SharedStyleContext};
+use style::font_metrics::{FontMetrics, FontMetricsProvider, FontMetricsQueryResult};
+use style::logical_geometry::WritingMode;
+use style::media_queries::{Device as StyleDevice, MediaType};
+use style::properties::style_structs::Font;
+use style::servo::media_queries::Device;
+use style::values::computed::{AspectRatio as ComputedAspectRatio, CalcLengthOrPercentage};
+use style::values::computed::{Context, ContextBuilder, Percentage};
+use style::values::computed::ToComputedValue;
+use style::values::computed::{Length, LengthOrPercentage as ComputedLengthOrPercentage};
+use style::values::computed::image::LineDirection;
+use style::values::generics::box_::{AspectRatio as GenericAspectRatio, Ratio};
+use style::values::generics::image::{GradientKind, Image as GenericImage};
+use style::values::specified::{AspectRatio, Color, ColorPropertyValue, RGBAColor};
+use style::values::specified::{AbsoluteLength, FontRelativeLength, LengthOrPercentage, NoCalcLength};
+use style::values::specified::image::Image;
+use style::values::specified::position::{Position, PositionComponent, X, Y};
+use style_traits::ToCss;
+
+/// Returns the 800x600 screen device that `Context::for_testing` computes
+/// against, for tests that need to customize the context with a
+/// `ContextBuilder`.
+fn test_device() -> Device {
+    Device::new(MediaType::screen(), TypedSize2D::new(800.0, 600.0), TypedScale::new(1.0))
+}
+
+/// A font metrics provider that reports a fixed x-height.
+struct XHeightMetricsProvider(Au);
+
+impl FontMetricsProvider for XHeightMetricsProvider {
+    fn query(
+        &self,
+        _font: &Font,
+        _font_size: Au,
+        _wm: WritingMode,
+        _in_media_query: bool,
+        _device: &StyleDevice,
+    ) -> FontMetricsQueryResult {
+        FontMetricsQueryResult::Available(FontMetrics {
+            x_height: self.0,
+            zero_advance_measure: Au(0),
+        })
+    }
+
+    fn get_size(&self, _font_name: &Atom, _font_family: u8) -> Au {
+        unreachable!("Should never be used to compute font size")
+    }
+
+    fn create_from(_: &SharedStyleContext) -> Self {
+        unreachable!("Should never be created from a style context")
+    }
+}
+
+#[test]
+fn test_currentcolor_resolves_against_context_color() {
+    let blue = RGBA::new(0, 0, 255, 255);
+    ContextBuilder::new(&test_device()).with_color(blue).build(|context| {
+        // `border-color: currentColor` uses the element's own color.
+        let border_color = Color::currentcolor().to_computed_value(context);
+        assert!(border_color.is_currentcolor());
+        assert_eq!(border_color.to_rgba(context.style().get_color().clone_color()), blue);
+
+        let rgba = RGBAColor(Color::currentcolor());
+        assert_eq!(rgba.to_computed_value(context), blue);
+    });
+}
+
+#[test]
+fn test_currentcolor_on_color_property_uses_inherited_color() {
+    let blue = RGBA::new(0, 0, 255, 255);
+    ContextBuilder::new(&test_device()).with_color(blue).build(|context| {
+        // The initial (inherited) color is black, not the element's own color.
+        let color = ColorPropertyValue(Color::currentcolor());
+        assert_eq!(color.to_computed_value(context), RGBA::new(0, 0, 0, 255));
+    });
+}
+
+#[test]
+fn test_position_edge_offsets() {
+    let px = |value| LengthOrPercentage::Length(NoCalcLength::from_px(value));
+    Context::for_testing(|context| {
+        // `right 10px bottom 20px`
+        let position = Position::new(
+            PositionComponent::Side(X::Right, Some(px(10.))),
+            PositionComponent::Side(Y::Bottom, Some(px(20.))),
+        );
+        let computed = position.to_computed_value(context);
+        assert_eq!(computed.horizontal, ComputedLengthOrPercentage::Calc(
+            CalcLengthOrPercentage::new(Length::new(-10.), Some(Percentage::hundred()))));
+        assert_eq!(computed.vertical, ComputedLengthOrPercentage::Calc(
+            CalcLengthOrPercentage::new(Length::new(-20.), Some(Percentage::hundred()))));
+
+        // `left 10px top`
+        let position = Position::new(
+            PositionComponent::Side(X::Left, Some(px(10.))),
+            PositionComponent::Side(Y::Top, None),
+        );
+        let computed = position.to_computed_value(context);
+        assert_eq!(computed.horizontal, ComputedLengthOrPercentage::Length(Length::new(10.)));
+        assert_eq!(computed.vertical, ComputedLengthOrPercentage::Percentage(Percentage(0.)));
+
+        // `center`
+        let position = Position::new(PositionComponent::Center, PositionComponent::Center);
+        let computed = position.to_computed_value(context);
+        assert_eq!(computed.horizontal, ComputedLengthOrPercentage::Percentage(Percentage(0.5)));
+        assert_eq!(computed.vertical, ComputedLengthOrPercentage::Percentage(Percentage(0.5)));
+    });
+}
+
+#[test]
+fn test_ex_uses_font_metrics() {
+    let ex = NoCalcLength::FontRelative(FontRelativeLength::Ex(2.));
+
+    let provider = XHeightMetricsProvider(Au::from_px(7));
+    ContextBuilder::new(&test_device()).with_font_metrics_provider(&provider).build(|context| {
+        assert_eq!(ex.to_computed_value(context), Length::new(14.));
+    });
+
+    // Without metrics, the x-height is assumed to be 0.5em of the initial
+    // 16px font size.
+    Context::for_testing(|context| {
+        assert_eq!(ex.to_computed_value(context), Length::new(16.));
+    });
+}
+
+#[test]
+fn test_huge_lengths_saturate() {
+    assert_eq!(Au::from(Length::new(100000000.)), MAX_AU);
+    assert_eq!(Au::from(Length::new(-100000000.)), MIN_AU);
+    assert_eq!(Length::new(100000000.).to_i32_au(), MAX_AU.0);
+
+    Context::for_testing(|context| {
+        let huge = NoCalcLength::from_px(100000000.).to_computed_value(context);
+        assert_eq!(Au::from(huge), MAX_AU);
+
+        let huge = NoCalcLength::Absolute(AbsoluteLength::In(100000000.)).to_computed_value(context);
+        assert_eq!(Au::from(huge), MAX_AU);
+    });
+}
+
+#[test]
+fn test_gradient_stop_positions_are_monotonic() {
+    Context::for_testing(|context| {
+        let compute = |s| parse_value::<Image>(s).to_computed_value(context).to_css_string();
+
+        assert_eq!(compute("linear-gradient(red 50%, blue 30%)"),
+                   "linear-gradient(rgb(255, 0, 0) 50%, rgb(0, 0, 255) 50%)");
+        assert_eq!(compute("linear-gradient(red 20px, 10px, blue 5px, green 30px)"),
+                   "linear-gradient(rgb(255, 0, 0) 20px, 20px, rgb(0, 0, 255) 20px, rgb(0, 128, 0) 30px)");
+
+        // Stops without a position, or with positions in different units, are
+        // left alone.
+        assert_eq!(compute("linear-gradient(red 50%, blue, green 10px)"),
+                   "linear-gradient(rgb(255, 0, 0) 50%, rgb(0, 0, 255), rgb(0, 128, 0) 10px)");
+    });
+}
+
+/// Computes the direction of a linear gradient, in the modern syntax.
+fn linear_gradient_direction(context: &Context, s: &str) -> LineDirection {
+    match parse_value::<Image>(s).to_computed_value(context) {
+        GenericImage::Gradient(gradient) => match gradient.kind {
+            GradientKind::Linear(direction) => direction.to_modern(gradient.compat_mode),
+            _ => panic!("{} is not a linear gradient", s),
+        },
+        _ => panic!("{} is not a gradient", s),
+    }
+}
+
+#[test]
+fn test_webkit_linear_gradient_direction() {
+    Context::for_testing(|context| {
+        let equivalent = [
+            ("-webkit-linear-gradient(left, red, blue)", "linear-gradient(to right, red, blue)"),
+            ("-webkit-linear-gradient(bottom, red, blue)", "linear-gradient(to top, red, blue)"),
+            ("-webkit-linear-gradient(top left, red, blue)",
+             "linear-gradient(to bottom right, red, blue)"),
+            ("-webkit-linear-gradient(0deg, red, blue)", "linear-gradient(90deg, red, blue)"),
+            ("-webkit-linear-gradient(90deg, red, blue)", "linear-gradient(0deg, red, blue)"),
+            ("-webkit-linear-gradient(red, blue)", "linear-gradient(red, blue)"),
+        ];
+        for &(legacy, modern) in equivalent.iter() {
+            assert_eq!(linear_gradient_direction(context, legacy),
+                       linear_gradient_direction(context, modern),
+                       "{} should be equivalent to {}", legacy, modern);
+        }
+    });
+}
+
+#[test]
+fn test_linear_gradient_angle_normalization() {
+    use style::values::computed::Angle;
+
+    Context::for_testing(|context| {
+        let normalized = |s: &str| linear_gradient_direction(context, s).normalized();
+        assert_eq!(normalized("linear-gradient(-45deg, red, blue)"), LineDirection::Angle(Angle::Deg(315.)));
+        assert_eq!(normalized("linear-gradient(450deg, red, blue)"), LineDirection::Angle(Angle::Deg(90.)));
+        assert_eq!(normalized("linear-gradient(45deg, red, blue)"), LineDirection::Angle(Angle::Deg(45.)));
+        // Prefixed angles can go out of range when converted to the modern
+        // syntax.
+        assert_eq!(normalized("-webkit-linear-gradient(180deg, red, blue)"),
+                   LineDirection::Angle(Angle::Deg(270.)));
+        // Keywords are left alone.
+        assert_eq!(normalized("linear-gradient(to left, red, blue)"), LineDirection::Horizontal(X::Left));
+
+        // The authored angle is kept for serialization.
+        let image = parse_value::<Image>("linear-gradient(-45deg, red, blue)").to_computed_value(context);
+        assert_eq!(image.to_css_string(), "linear-gradient(-45deg, rgb(255, 0, 0), rgb(0, 0, 255))");
+        let image = parse_value::<Image>("linear-gradient(450deg, red, blue)").to_computed_value(context);
+        assert_eq!(image.to_css_string(), "linear-gradient(450deg, rgb(255, 0, 0), rgb(0, 0, 255))");
+    });
+}
+
+#[test]
+fn test_angle_degrees_and_normalization() {
+    use std::f32::consts::PI;
+    use style::values::computed::Angle;
+
+    assert!((Angle::from_radians(PI).degrees() - 180.).abs() < 1e-4);
+    assert!((Angle::Turn(0.25).degrees() - 90.).abs() < 1e-4);
+
+    let normalized = Angle::from_radians(3. * PI).normalized();
+    assert!((normalized.radians() - PI).abs() < 1e-5, "{:?}", normalized);
+    assert_eq!(Angle::Deg(540.).normalized(), Angle::Deg(180.));
+    assert_eq!(Angle::Deg(-90.).normalized(), Angle::Deg(270.));
+    assert_eq!(Angle::Deg(360.).normalized(), Angle::Deg(0.));
+    assert_eq!(Angle::Grad(500.).normalized(), Angle::Grad(100.));
+    assert_eq!(Angle::Turn(1.5).normalized(), Angle::Turn(0.5));
+    // Tiny negative angles don't wrap around to a full turn.
+    assert_eq!(Angle::Deg(-1e-6).normalized(), Angle::Deg(0.));
+    assert_eq!(Angle::Turn(-1e-9).normalized(), Angle::Turn(0.));
+}
+
+#[test]
+fn test_angle_arithmetic() {
+    use std::f32::consts::PI;
+    use style::values::computed::Angle;
+
+    let pi = Angle::from_radians(PI);
+    assert_eq!(pi + pi, Angle::from_radians(2. * PI));
+    assert_eq!(pi - pi, Angle::from_radians(0.));
+    assert_eq!(pi * 2., Angle::from_radians(2. * PI));
+    assert_eq!(pi / 2., Angle::from_radians(PI / 2.));
+
+    // Operations on the same unit keep that unit.
+    assert_eq!(Angle::Deg(90.) + Angle::Deg(45.), Angle::Deg(135.));
+    assert_eq!(Angle::Deg(90.) - Angle::Deg(45.), Angle::Deg(45.));
+    assert_eq!(Angle::Turn(0.25) * 3., Angle::Turn(0.75));
+    assert_eq!(Angle::Grad(100.) / 4., Angle::Grad(25.));
+
+    // Mixed units fall back to radians, and keep their ordering.
+    let sum = Angle::Deg(180.) + Angle::Turn(0.5);
+    assert!((sum.radians() - 2. * PI).abs() < 1e-5, "{:?}", sum);
+    assert!(Angle::from_radians(PI / 2.) < pi);
+    assert!(pi * 0.5 < pi);
+    assert!(pi < pi + Angle::from_radians(0.1));
+}
+
+#[test]
+fn test_to_computed_value_method_form() {
+    use style::values::computed::LengthOrPercentageOrAuto as ComputedLengthOrPercentageOrAuto;
+    use style::values::specified::LengthOrPercentageOrAuto;
+
+    Context::for_testing(|context| {
+        let length: LengthOrPercentage = parse_value("10px");
+        assert_eq!(length.to_computed_value(context),
+                   ComputedLengthOrPercentage::Length(Length::new(10.)));
+
+        let percentage: LengthOrPercentage = parse_value("50%");
+        assert_eq!(percentage.to_computed_value(context),
+                   ComputedLengthOrPercentage::Percentage(Percentage(0.5)));
+
+        let auto: LengthOrPercentageOrAuto = parse_value("auto");
+        assert_eq!(auto.to_computed_value(context), ComputedLengthOrPercentageOrAuto::Auto);
+
+        let color: RGBAColor = parse_value("red");
+        assert_eq!(color.to_computed_value(context), RGBA::new(255, 0, 0, 255));
+
+        let image: Image = parse_value("url(foo.png)");
+        assert_eq!(image.to_computed_value(context).to_css_string(),
+                   "url(\"http://localhost/foo.png\")");
+    });
+}
+
+#[test]
+fn test_conic_gradient_stop_units() {
+    Context::for_testing(|context| {
+        let compute = |s: &str| parse_value::<Image>(s).to_computed_value(context).to_css_string();
+        // Angles keep their authored unit through computation, so they don't
+        // come back in radians.
+        assert_eq!(compute("conic-gradient(red 0deg, blue 180deg)"),
+                   "conic-gradient(rgb(255, 0, 0) 0deg, rgb(0, 0, 255) 180deg)");
+        assert_eq!(compute("conic-gradient(from 0.25turn, red 0.5turn, blue 300grad)"),
+                   "conic-gradient(from 0.25turn, rgb(255, 0, 0) 0.5turn, rgb(0, 0, 255) 300grad)");
+    });
+}
+
+#[test]
+fn test_conic_gradient_stop_fixup() {
+    Context::for_testing(|context| {
+        let compute = |s: &str| parse_value::<Image>(s).to_computed_value(context).to_css_string();
+        // Out of order angles are clamped to the largest previous one,
+        // whatever their unit.
+        assert_eq!(compute("conic-gradient(red 180deg, blue 90deg)"),
+                   "conic-gradient(rgb(255, 0, 0) 180deg, rgb(0, 0, 255) 180deg)");
+        assert_eq!(compute("conic-gradient(red 0.5turn, blue 90deg, lime 270deg)"),
+                   "conic-gradient(rgb(255, 0, 0) 0.5turn, rgb(0, 0, 255) 0.5turn, rgb(0, 255, 0) 270deg)");
+        // Angles and percentages aren't compared with each other.
+        assert_eq!(compute("conic-gradient(red 50%, blue 20%, lime 10deg)"),
+                   "conic-gradient(rgb(255, 0, 0) 50%, rgb(0, 0, 255) 50%, rgb(0, 255, 0) 10deg)");
+        // Duplicate stops are collapsed, after the fixup.
+        assert_eq!(compute("conic-gradient(red, blue 90deg, blue 45deg, lime)"),
+                   "conic-gradient(rgb(255, 0, 0), rgb(0, 0, 255) 90deg, rgb(0, 255, 0))");
+    });
+}
+
+#[test]
+fn test_radial_gradient_ending_shapes() {
+    use style::values::generics::image::{Circle, Ellipse, EndingShape, ShapeExtent};
+
+    Context::for_testing(|context| {
+        let shape = |s: &str| match parse_value::<Image>(s).to_computed_value(context) {
+            GenericImage::Gradient(gradient) => match gradient.kind {
+                GradientKind::Radial(shape, ..) => shape,
+                _ => panic!("{} is not a radial gradient", s),
+            },
+            _ => panic!("{} is not a gradient", s),
+        };
+
+        assert_eq!(shape("radial-gradient(closest-side circle, red, blue)"),
+                   EndingShape::Circle(Circle::Extent(ShapeExtent::ClosestSide)));
+        assert_eq!(shape("radial-gradient(closest-corner, red, blue)"),
+                   EndingShape::Ellipse(Ellipse::Extent(ShapeExtent::ClosestCorner)));
+        assert_eq!(shape("radial-gradient(red, blue)"),
+                   EndingShape::Ellipse(Ellipse::Extent(ShapeExtent::FarthestCorner)));
+        assert_eq!(shape("radial-gradient(circle 2em, red, blue)"),
+                   EndingShape::Circle(Circle::Radius(Length::new(32.))));
+        assert_eq!(shape("radial-gradient(10px 50%, red, blue)"),
+                   EndingShape::Ellipse(Ellipse::Radii(
+                       ComputedLengthOrPercentage::Length(Length::new(10.)),
+                       ComputedLengthOrPercentage::Percentage(Percentage(0.5)),
+                   )));
+    });
+}
+
+#[test]
+fn test_transparent_computes_to_zero_alpha() {
+    Context::for_testing(|context| {
+        let color: RGBAColor = parse_value("transparent");
+        let computed = color.to_computed_value(context);
+        assert_eq!(computed, RGBA::new(0, 0, 0, 0));
+        assert_eq!(computed.alpha, 0);
+
+        let color: Color = parse_value("transparent");
+        assert_eq!(color.to_computed_value(context).to_rgba(RGBA::new(0, 0, 0, 255)),
+                   RGBA::transparent());
+    });
+}
+
+#[test]
+fn test_color_alpha_and_opacity() {
+    let black = RGBA::new(0, 0, 0, 255);
+    Context::for_testing(|context| {
+        let color = parse_value::<Color>("#0a0b0c").to_computed_value(context);
+        assert_eq!(color.alpha(black), 1.);
+        assert!(color.is_opaque(black));
+
+        let color = parse_value::<Color>("rgba(255, 0, 0, 0.5)").to_computed_value(context);
+        assert_eq!(color.alpha(black), 128. / 255.);
+        assert!(!color.is_opaque(black));
+
+        let color = parse_value::<Color>("transparent").to_computed_value(context);
+        assert_eq!(color.alpha(black), 0.);
+        assert!(!color.is_opaque(black));
+
+        // currentcolor takes the alpha of the foreground color.
+        let color = parse_value::<Color>("currentcolor").to_computed_value(context);
+        assert!(color.is_opaque(black));
+        assert!(!color.is_opaque(RGBA::transparent()));
+    });
+}
+
+#[test]
+fn test_cross_fade_computes_nested_images() {
+    Context::for_testing(|context| {
+        let image: Image = parse_value("cross-fade(url(a.png) 50%, linear-gradient(red, blue))");
+        assert_eq!(image.to_computed_value(context).to_css_string(),
+                   "cross-fade(url(\"http://localhost/a.png\") 50%, \
+                    linear-gradient(rgb(255, 0, 0), rgb(0, 0, 255)))");
+    });
+}
+
+#[test]
+fn test_image_set_computes_all_candidates() {
+    Context::for_testing(|context| {
+        let image: Image = parse_value("image-set(url(a.png) 1x, url(b.png) 192dpi)");
+        assert_eq!(image.to_computed_value(context).to_css_string(),
+                   "image-set(url(\"http://localhost/a.png\") 1dppx, \
+                    url(\"http://localhost/b.png\") 2dppx)");
+    });
+}
+
+#[test]
+fn test_percentage_serialization() {
+    use style::values::computed::{LengthOrPercentageOrAuto, LengthOrPercentageOrNone};
+
+    assert_eq!(Percentage(0.5).to_css_string(), "50%");
+    assert_eq!(Percentage(1. / 3.).to_css_string(), "33.3333%");
+    assert_eq!(Percentage(0.29).to_css_string(), "29%");
+    assert_eq!(Percentage(0.125).to_css_string(), "12.5%");
+
+    assert_eq!(ComputedLengthOrPercentage::Percentage(Percentage(0.5)).to_css_string(), "50%");
+    assert_eq!(LengthOrPercentageOrAuto::Percentage(Percentage(1. / 3.)).to_css_string(), "33.3333%");
+    assert_eq!(LengthOrPercentageOrNone::Percentage(Percentage(0.29)).to_css_string(), "29%");
+
+    let calc = CalcLengthOrPercentage::new(Length::new(10.), Some(Percentage(2. / 3.)));
+    assert_eq!(calc.to_css_string(), "calc(66.6667% + 10px)");
+
+    // Rounding doesn't overflow for huge percentages, like `width: 1e35%`.
+    assert_eq!(Percentage(1e33).to_css_string(), format!("{}%", (1e33f32 * 100.).to_css_string()));
+    assert!(!Percentage(1e36).to_css_string().contains("inf"));
+}
+
+#[test]
+fn test_parsed_position_edge_offsets_compute_to_calc() {
+    Context::for_testing(|context| {
+        let position = parse_value::<Position>("right 10px bottom 25%").to_computed_value(context);
+        assert_eq!(position.horizontal.to_css_string(), "calc(100% - 10px)");
+        assert_eq!(position.vertical, ComputedLengthOrPercentage::Percentage(Percentage(0.75)));
+
+        let position = parse_value::<Position>("right calc(10px + 20%) top").to_computed_value(context);
+        assert_eq!(position.horizontal, ComputedLengthOrPercentage::Calc(
+            CalcLengthOrPercentage::new(Length::new(-10.), Some(Percentage(0.8)))));
+        assert_eq!(position.horizontal.to_css_string(), "calc(80% - 10px)");
+    });
+}
+
+#[test]
+fn test_length_or_percentage_used_values() {
+    use style::values::computed::{LengthOrPercentageOrAuto, LengthOrPercentageOrNone};
+
+    let basis = Au::from_px(200);
+    let calc = CalcLengthOrPercentage::new(Length::new(10.), Some(Percentage(0.5)));
+
+    assert_eq!(ComputedLengthOrPercentage::Length(Length::new(10.)).to_used_value(basis), Au::from_px(10));
+    assert_eq!(ComputedLengthOrPercentage::Percentage(Percentage(0.25)).to_used_value(basis), Au::from_px(50));
+    assert_eq!(ComputedLengthOrPercentage::Calc(calc).to_used_value(basis), Au::from_px(110));
+
+    assert_eq!(LengthOrPercentageOrAuto::Length(Length::new(10.)).to_used_value(basis), Some(Au::from_px(10)));
+    assert_eq!(LengthOrPercentageOrAuto::Percentage(Percentage(0.25)).to_used_value(basis), Some(Au::from_px(50)));
+    assert_eq!(LengthOrPercentageOrAuto::Calc(calc).to_used_value(basis), Some(Au::from_px(110)));
+    assert_eq!(LengthOrPercentageOrAuto::Auto.to_used_value(basis), None);
+
+    assert_eq!(LengthOrPercentageOrNone::Length(Length::new(10.)).to_used_value(basis), Some(Au::from_px(10)));
+    assert_eq!(LengthOrPercentageOrNone::Percentage(Percentage(0.25)).to_used_value(basis), Some(Au::from_px(50)));
+    assert_eq!(LengthOrPercentageOrNone::Calc(calc).to_used_value(basis), Some(Au::from_px(110)));
+    assert_eq!(LengthOrPercentageOrNone::None.to_used_value(basis), None);
+}
+
+#[test]
+fn test_length_or_percentage_constructors() {
+    use style::values::computed::{LengthOrPercentageOrAuto, LengthOrPercentageOrNone};
+
+    let lop = ComputedLengthOrPercentage::from_au(Au::from_px(10));
+    assert_eq!(lop, ComputedLengthOrPercentage::Length(Length::new(10.)));
+    assert_eq!(lop.to_css_string(), "10px");
+    let lop = ComputedLengthOrPercentage::from_percentage(0.5);
+    assert_eq!(lop, ComputedLengthOrPercentage::Percentage(Percentage(0.5)));
+    assert_eq!(lop.to_css_string(), "50%");
+
+    let lopa = LengthOrPercentageOrAuto::from_au(Au::from_px(10));
+    assert_eq!(lopa, LengthOrPercentageOrAuto::Length(Length::new(10.)));
+    assert_eq!(lopa.to_css_string(), "10px");
+    let lopa = LengthOrPercentageOrAuto::from_percentage(0.25);
+    assert_eq!(lopa, LengthOrPercentageOrAuto::Percentage(Percentage(0.25)));
+    assert_eq!(lopa.to_css_string(), "25%");
+
+    let lopn = LengthOrPercentageOrNone::from_au(Au(30));
+    assert_eq!(lopn, LengthOrPercentageOrNone::Length(Length::new(0.5)));
+    assert_eq!(lopn.to_css_string(), "0.5px");
+    let lopn = LengthOrPercentageOrNone::from_percentage(1.);
+    assert_eq!(lopn, LengthOrPercentageOrNone::Percentage(Percentage(1.)));
+    assert_eq!(lopn.to_css_string(), "100%");
+}
+
+#[test]
+fn test_length_or_percentage_resolve() {
+    use style::values::computed::{LengthOrPercentageOrAuto, LengthOrPercentageOrNone};
+    use style::values::computed::{NonNegativeLengthOrPercentage, PercentageBasis};
+    use style::values::computed::PercentageBasis::{Height, Width};
+
+    let containing_block = Size2D::new(Au::from_px(200), Au::from_px(100));
+    assert_eq!(PercentageBasis::Width.length(containing_block), Au::from_px(200));
+    assert_eq!(PercentageBasis::Height.length(containing_block), Au::from_px(100));
+
+    let calc = CalcLengthOrPercentage::new(Length::new(10.), Some(Percentage(0.5)));
+
+    let lop = ComputedLengthOrPercentage::Percentage(Percentage(0.25));
+    assert_eq!(lop.resolve_for(Width, containing_block), Au::from_px(50));
+    assert_eq!(lop.resolve_for(Height, containing_block), Au::from_px(25));
+    assert_eq!(ComputedLengthOrPercentage::Length(Length::new(10.)).resolve_for(Height, containing_block),
+               Au::from_px(10));
+    assert_eq!(ComputedLengthOrPercentage::Calc(calc).resolve_for(Height, containing_block),
+               Au::from_px(60));
+
+    let non_negative = NonNegativeLengthOrPercentage::from(lop);
+    assert_eq!(non_negative.resolve_for(Width, containing_block), Au::from_px(50));
+
+    assert_eq!(LengthOrPercentageOrAuto::Percentage(Percentage(0.25)).resolve_for(Height, containing_block),
+               Some(Au::from_px(25)));
+    assert_eq!(LengthOrPercentageOrAuto::Calc(calc).resolve_for(Width, containing_block),
+               Some(Au::from_px(110)));
+    assert_eq!(LengthOrPercentageOrAuto::Auto.resolve_for(Width, containing_block), None);
+
+    assert_eq!(LengthOrPercentageOrNone::Percentage(Percentage(0.25)).resolve_for(Height, containing_block),
+               Some(Au::from_px(25)));
+    assert_eq!(LengthOrPercentageOrNone::Calc(calc).resolve_for(Width, containing_block),
+               Some(Au::from_px(110)));
+    assert_eq!(LengthOrPercentageOrNone::None.resolve_for(Width, containing_block), None);
+}
+
+#[test]
+fn test_context_for_testing() {
+    Context::for_testing(|context| {
+        let em = NoCalcLength::FontRelative(FontRelativeLength::Em(1.));
+        assert_eq!(Au::from(em.to_computed_value(context)), Au::from_px(16));
+        assert_eq!(context.style().get_color().clone_color(), RGBA::new(0, 0, 0, 255));
+        assert!(!context.is_root_element());
+        assert!(!context.in_media_query);
+        assert_eq!(context.quirks_mode, QuirksMode::NoQuirks);
+        assert_eq!(context.viewport_size_for_viewport_unit_resolution(),
+                   Size2D::new(Au::from_px(800), Au::from_px(600)));
+    });
+}
+
+#[test]
+fn test_context_builder() {
+    let device = test_device();
+    let em = NoCalcLength::FontRelative(FontRelativeLength::Em(2.));
+
+    // The defaults use the initial style of the device.
+    ContextBuilder::new(&device).build(|context| {
+        assert!(!context.is_root_element());
+        assert_eq!(em.to_computed_value(context), Length::new(32.));
+        assert_eq!(context.style().get_color().clone_color(), RGBA::new(0, 0, 0, 255));
+    });
+
+    let blue = RGBA::new(0, 0, 255, 255);
+    ContextBuilder::new(&device)
+        .with_root_element(true)
+        .with_font_size(Length::new(10.))
+        .with_color(blue)
+        .build(|context| {
+            assert!(context.is_root_element());
+            assert_eq!(em.to_computed_value(context), Length::new(20.));
+            assert_eq!(context.style().get_color().clone_color(), blue);
+        });
+
+    let provider = XHeightMetricsProvider(Au::from_px(7));
+    let ex = NoCalcLength::FontRelative(FontRelativeLength::Ex(2.));
+    ContextBuilder::new(&device).with_font_metrics_provider(&provider).build(|context| {
+        assert_eq!(ex.to_computed_value(context), Length::new(14.));
+    });
+}
+
+#[test]
+fn test_length_or_percentage_to_specified() {
+    use style::values::computed::{LengthOrPercentageOrAuto, LengthOrPercentageOrNone};
+    use style::values::specified::LengthOrPercentageOrAuto as SpecifiedLengthOrPercentageOrAuto;
+    use style::values::specified::LengthOrPercentageOrNone as SpecifiedLengthOrPercentageOrNone;
+
+    let px = NoCalcLength::from_px(10.);
+    assert_eq!(ComputedLengthOrPercentage::Length(Length::new(10.)).to_specified(),
+               LengthOrPercentage::Length(px));
+    assert_eq!(ComputedLengthOrPercentage::Percentage(Percentage(0.5)).to_specified(),
+               LengthOrPercentage::Percentage(Percentage(0.5)));
+    assert_eq!(LengthOrPercentageOrAuto::Length(Length::new(10.)).to_specified(),
+               SpecifiedLengthOrPercentageOrAuto::Length(px));
+    assert_eq!(LengthOrPercentageOrAuto::Percentage(Percentage(0.5)).to_specified(),
+               SpecifiedLengthOrPercentageOrAuto::Percentage(Percentage(0.5)));
+    assert_eq!(LengthOrPercentageOrAuto::Auto.to_specified(), SpecifiedLengthOrPercentageOrAuto::Auto);
+    assert_eq!(LengthOrPercentageOrNone::Length(Length::new(10.)).to_specified(),
+               SpecifiedLengthOrPercentageOrNone::Length(px));
+    assert_eq!(LengthOrPercentageOrNone::Percentage(Percentage(0.5)).to_specified(),
+               SpecifiedLengthOrPercentageOrNone::Percentage(Percentage(0.5)));
+    assert_eq!(LengthOrPercentageOrNone::None.to_specified(), SpecifiedLengthOrPercentageOrNone::None);
+
+    // Font-relative lengths come back as pixels.
+    let device = test_device();
+    ContextBuilder::new(&device).with_font_size(Length::new(10.)).build(|context| {
+        let computed = parse_value::<LengthOrPercentage>("2em").to_computed_value(context);
+        assert_eq!(computed.to_specified().to_css_string(), "20px");
+        let computed = parse_value::<LengthOrPercentage>("calc(1em + 50%)").to_computed_value(context);
+        assert_eq!(computed.to_specified().to_computed_value(context), computed);
+    });
+}
+
+#[test]
+fn test_percentage_resolution_saturates() {
+    use style::values::computed::{LengthOrPercentageOrAuto, LengthOrPercentageOrNone};
+
+    let huge = Au(i32::max_value() - 1);
+    let hundred = Percentage::hundred();
+    assert_eq!(ComputedLengthOrPercentage::Percentage(hundred).to_used_value(huge), MAX_AU);
+    assert_eq!(LengthOrPercentageOrAuto::Percentage(hundred).to_used_value(huge), Some(MAX_AU));
+    assert_eq!(LengthOrPercentageOrNone::Percentage(hundred).to_used_value(huge), Some(MAX_AU));
+    let calc = CalcLengthOrPercentage::new(Length::new(10.), Some(hundred));
+    assert_eq!(ComputedLengthOrPercentage::Calc(calc).to_used_value(huge), MAX_AU);
+
+    let negative = Percentage(-2.);
+    assert_eq!(LengthOrPercentageOrAuto::Percentage(negative).to_used_value(huge), Some(MIN_AU));
+
+    // In-range values are unaffected.
+    let basis = Au::from_px(200);
+    assert_eq!(LengthOrPercentageOrAuto::Percentage(Percentage(0.5)).to_used_value(basis),
+               Some(Au::from_px(100)));
+    assert_eq!(ComputedLengthOrPercentage::Percentage(Percentage(0.5)).to_used_value(basis),
+               Au::from_px(100));
+}
+
+#[test]
+fn test_length_or_percentage_hash() {
+    use std::collections::HashSet;
+
+    let calc = CalcLengthOrPercentage::new(Length::new(10.), Some(Percentage(0.5)));
+    let mut set = HashSet::new();
+    assert!(set.insert(ComputedLengthOrPercentage::Length(Length::new(10.))));
+    assert!(!set.insert(ComputedLengthOrPercentage::Length(Length::new(10.))));
+    assert_eq!(set.len(), 1);
+
+    // Lengths and percentages with the same number are different values.
+    assert!(set.insert(ComputedLengthOrPercentage::Percentage(Percentage(10.))));
+    assert!(set.insert(ComputedLengthOrPercentage::Calc(calc)));
+    assert!(!set.insert(ComputedLengthOrPercentage::Calc(calc)));
+    assert_eq!(set.len(), 3);
+
+    // Positive and negative zero are equal, so they hash the same.
+    assert!(set.insert(ComputedLengthOrPercentage::Length(Length::new(0.))));
+    assert!(!set.insert(ComputedLengthOrPercentage::Length(Length::new(-0.))));
+    assert_eq!(set.len(), 4);
+}
+
+#[test]
+fn test_linear_gradient_default_direction_is_omitted() {
+    Context::for_testing(|context| {
+        for &(input, expected) in [
+            ("linear-gradient(red, blue)", "linear-gradient(rgb(255, 0, 0), rgb(0, 0, 255))"),
+            ("linear-gradient(to bottom, red, blue)", "linear-gradient(rgb(255, 0, 0), rgb(0, 0, 255))"),
+            ("linear-gradient(to top, red, blue)", "linear-gradient(to top, rgb(255, 0, 0), rgb(0, 0, 255))"),
+            ("-webkit-linear-gradient(top, red, blue)",
+             "-webkit-linear-gradient(rgb(255, 0, 0), rgb(0, 0, 255))"),
+        ].iter() {
+            let specified = parse_value::<Image>(input);
+            assert!(!specified.to_css_string().contains("3.14159"), "{}", input);
+            assert_eq!(specified.to_computed_value(context).to_css_string(), expected);
+        }
+    });
+}
+
+#[test]
+fn test_length_to_au_rounds() {
+    // 0.995px is 59.7 app units, which rounds up rather than truncating.
+    assert_eq!(Au::from(Length::new(0.995)), Au(60));
+    assert_eq!(Au::from(Length::new(-0.995)), Au(-60));
+    assert_eq!(Au::from(Length::new(0.99)), Au(59));
+    assert_eq!(Au::from(Length::new(0.9)), Au(54));
+    assert_eq!(Length::new(0.995).to_i32_au(), 60);
+
+    Context::for_testing(|context| {
+        let au = |length: AbsoluteLength| Au::from(NoCalcLength::Absolute(length).to_computed_value(context));
+
+        assert_eq!(au(AbsoluteLength::Px(0.995)), Au(60));
+        // 1in is exactly 96px.
+        assert_eq!(au(AbsoluteLength::In(1.)), Au::from_px(96));
+        // 1cm is 2267.72 app units, and 1mm is 226.77 app units.
+        assert_eq!(au(AbsoluteLength::Cm(1.)), Au(2268));
+        assert_eq!(au(AbsoluteLength::Mm(1.)), Au(227));
+        assert_eq!(au(AbsoluteLength::Mm(-1.)), Au(-227));
+        // 1pt is exactly 80 app units.
+        assert_eq!(au(AbsoluteLength::Pt(1.)), Au(80));
+    });
+}
+
+#[test]
+fn test_computed_gradient_equality() {
+    Context::for_testing(|context| {
+        let compute = |s: &str| parse_value::<Image>(s).to_computed_value(context);
+
+        assert!(compute("linear-gradient(to right, red, blue 50%)") ==
+                compute("linear-gradient(to right, red, blue 50%)"));
+        // Equivalent specified values compute to equal values.
+        assert!(compute("linear-gradient(90deg, #f00, rgb(0, 0, 255) 50%)") ==
+                compute("linear-gradient(90deg, red, blue 50%)"));
+
+        // A single differing stop position, color or direction makes them unequal.
+        assert!(compute("linear-gradient(to right, red, blue 50%)") !=
+                compute("linear-gradient(to right, red, blue 60%)"));
+        assert!(compute("linear-gradient(to right, red, blue 50%)") !=
+                compute("linear-gradient(to right, red, green 50%)"));
+        assert!(compute("linear-gradient(to right, red, blue 50%)") !=
+                compute("linear-gradient(to left, red, blue 50%)"));
+        assert!(compute("linear-gradient(to right, red, blue 50%)") !=
+                compute("repeating-linear-gradient(to right, red, blue 50%)"));
+    });
+}
+
+#[test]
+fn test_computed_aspect_ratio() {
+    Context::for_testing(|context| {
+        let compute = |s: &str| parse_value::<AspectRatio>(s).to_computed_value(context);
+
+        assert_eq!(compute("16 / 9"), GenericAspectRatio { auto: false, ratio: Some(Ratio(16., 9.)) });
+        assert_eq!(compute("1.5"), GenericAspectRatio { auto: false, ratio: Some(Ratio(1.5, 1.)) });
+        assert_eq!(compute("auto"), ComputedAspectRatio::auto());
+        assert_eq!(compute("4/3 auto"), GenericAspectRatio { auto: true, ratio: Some(Ratio(4., 3.)) });
+    });
+}
+
+#[test]
+fn test_calc_font_relative_lengths() {
+    use style::values::specified::Length as SpecifiedLength;
+
+    let device = test_device();
+    device.set_root_font_size(Au::from_px(20));
+    let calc = parse_value::<SpecifiedLength>("calc(2em + 3rem - 1ex)");
+
+    // 2 * 10px + 3 * 20px - 0.5 * 10px, since the x-height falls back to
+    // half the font size without metrics.
+    ContextBuilder::new(&device).with_font_size(Length::new(10.)).build(|context| {
+        let computed = calc.to_computed_value(context);
+        assert_eq!(computed, Length::new(75.));
+        assert_eq!(Au::from(computed), Au::from_px(75));
+    });
+
+    // 2 * 10px + 3 * 20px - 7px.
+    let provider = XHeightMetricsProvider(Au::from_px(7));
+    ContextBuilder::new(&device)
+        .with_font_size(Length::new(10.))
+        .with_font_metrics_provider(&provider)
+        .build(|context| {
+            let computed = calc.to_computed_value(context);
+            assert_eq!(computed, Length::new(73.));
+            assert_eq!(Au::from(computed), Au::from_px(73));
+        });
+}
+
+#[test]
+fn test_computed_values_to_css_string() {
+    use style::values::computed::{Angle, Color as ComputedColor};
+    use style::values::computed::{LengthOrPercentageOrAuto, LengthOrPercentageOrNone};
+
+    assert_eq!(Length::new(10.).to_css_string(), "10px");
+    assert_eq!(ComputedLengthOrPercentage::Length(Length::new(1.5)).to_css_string(), "1.5px");
+    assert_eq!(ComputedLengthOrPercentage::Percentage(Percentage(0.5)).to_css_string(), "50%");
+    assert_eq!(LengthOrPercentageOrAuto::Auto.to_css_string(), "auto");
+    assert_eq!(LengthOrPercentageOrAuto::Percentage(Percentage(0.25)).to_css_string(), "25%");
+    assert_eq!(LengthOrPercentageOrNone::None.to_css_string(), "none");
+    assert_eq!(LengthOrPercentageOrNone::Length(Length::new(0.)).to_css_string(), "0px");
+    assert_eq!(Angle::Deg(45.).to_css_string(), "45deg");
+    assert_eq!(Angle::Turn(0.5).to_css_string(), "0.5turn");
+    assert_eq!(ComputedColor::rgba(RGBA::new(255, 0, 0, 255)).to_css_string(), "rgb(255, 0, 0)");
+    assert_eq!(ComputedColor::currentcolor().to_css_string(), "currentcolor");
+
+    Context::for_testing(|context| {
+        let image = parse_value::<Image>("linear-gradient(to right, red, blue)").to_computed_value(context);
+        assert_eq!(image.to_css_string(),
+                   "linear-gradient(to right, rgb(255, 0, 0), rgb(0, 0, 255))");
+    });
+}
+
+#[test]
+fn test_radial_gradient_position_resolves_against_box() {
+    Context::for_testing(|context| {
+        let position = |s: &str| match parse_value::<Image>(s).to_computed_value(context) {
+            GenericImage::Gradient(gradient) => match gradient.kind {
+                GradientKind::Radial(_, position, _) => position,
+                _ => panic!("{} is not a radial gradient", s),
+            },
+            _ => panic!("{} is not a gradient", s),
+        };
+
+        let center = position("radial-gradient(circle at center, red, blue)");
+        assert_eq!(center.horizontal, ComputedLengthOrPercentage::Percentage(Percentage(0.5)));
+        assert_eq!(center.vertical, ComputedLengthOrPercentage::Percentage(Percentage(0.5)));
+
+        let percentages = position("radial-gradient(circle at 30% 70%, red, blue)");
+        assert_eq!(percentages.horizontal, ComputedLengthOrPercentage::Percentage(Percentage(0.3)));
+        assert_eq!(percentages.vertical, ComputedLengthOrPercentage::Percentage(Percentage(0.7)));
+
+        // `right 20px` is 20px from the right edge of the gradient box.
+        let offset = position("radial-gradient(circle at right 20px bottom, red, blue)");
+        assert_eq!(offset.horizontal, ComputedLengthOrPercentage::Calc(
+            CalcLengthOrPercentage::new(Length::new(-20.), Some(Percentage::hundred()))));
+        assert_eq!(offset.vertical, ComputedLengthOrPercentage::Percentage(Percentage(1.)));
+    });
+}
+
+#[test]
+fn test_image_function_compute() {
+    let device = test_device();
+    let blue = RGBA::new(0, 0, 255, 255);
+    ContextBuilder::new(&device).with_color(blue).build(|context| {
+        let compute = |s: &str| parse_value::<Image>(s).to_computed_value(context).to_css_string();
+        assert_eq!(compute("image(url(x.png), red)"),
+                   "image(url(\"http://localhost/x.png\"), rgb(255, 0, 0))");
+        assert_eq!(compute("image(currentcolor)"), "image(rgb(0, 0, 255))");
+    });
+}
+
+#[test]
+fn test_attr_image_is_not_resolved() {
+    Context::for_testing(|context| {
+        let specified = parse_value::<Image>("attr(data-img url)");
+        let computed = specified.to_computed_value(context);
+        match computed {
+            GenericImage::Attr(ref attr) => assert_eq!(&*attr.attribute, "data-img"),
+            _ => panic!("attr() should be left unresolved"),
+        }
+        assert_eq!(computed.to_css_string(), "attr(data-img url)");
+    });
+}
+
+#[test]
+fn test_compute_color_stops() {
+    use style::values::computed::image::compute_color_stops;
+    use style::values::generics::image::{ColorStop, GradientItem};
+
+    Context::for_testing(|context| {
+        let specified = |s: &str| match parse_value::<Image>(s) {
+            GenericImage::Gradient(gradient) => gradient,
+            _ => panic!("{} is not a gradient", s),
+        };
+
+        for s in &[
+            "linear-gradient(red, blue)",
+            "linear-gradient(red 10px, 20%, blue 3em)",
+            "radial-gradient(circle, red 10%, green 50%, blue)",
+        ] {
+            let gradient = specified(s);
+            let inline = gradient.items.iter()
+                .map(|item| item.to_computed_value(context))
+                .collect::<Vec<_>>();
+            let computed = compute_color_stops(&gradient.items, context);
+            assert_eq!(computed, inline);
+            assert_eq!(computed, gradient.to_computed_value(context).items);
+        }
+
+        // Out of order positions are clamped to the largest previous one.
+        let gradient = specified("linear-gradient(red 50%, blue 20%)");
+        let computed = compute_color_stops(&gradient.items, context);
+        assert_eq!(computed[1], GradientItem::ColorStop(ColorStop {
+            color: RGBA::new(0, 0, 255, 255),
+            position: Some(ComputedLengthOrPercentage::Percentage(Percentage(0.5))),
+        }));
+        assert_eq!(computed, gradient.to_computed_value(context).items);
+    });
+}
+
+#[test]
+fn test_compute_color_stops_collapses_duplicates() {
+    use style::values::computed::image::compute_color_stops;
+
+    Context::for_testing(|context| {
+        let items = |s: &str| match parse_value::<Image>(s) {
+            GenericImage::Gradient(gradient) => compute_color_stops(&gradient.items, context),
+            _ => panic!("{} is not a gradient", s),
+        };
+
+        assert_eq!(items("linear-gradient(red 10%, red 10%, blue 20%, lime)"),
+                   items("linear-gradient(red 10%, blue 20%, lime)"));
+        // Positions are fixed up first, so this becomes a duplicate too.
+        assert_eq!(items("linear-gradient(red, blue 50%, blue 20%, lime)"),
+                   items("linear-gradient(red, blue 50%, lime)"));
+
+        // Distinct stops are preserved.
+        assert_eq!(items("linear-gradient(red 10%, red 20%, blue)").len(), 3);
+        assert_eq!(items("linear-gradient(red 10%, blue 10%, lime)").len(), 3);
+        assert_eq!(items("linear-gradient(red 10px, red 10%, blue)").len(), 3);
+        // Stops without positions are spread out, so they're not duplicates.
+        assert_eq!(items("linear-gradient(red, red, blue)").len(), 3);
+        // Stops separated by an interpolation hint aren't adjacent.
+        assert_eq!(items("linear-gradient(red 10%, 10%, red 10%, blue)").len(), 4);
+        // At least two color stops are kept.
+        assert_eq!(items("linear-gradient(red 10%, red 10%)").len(), 2);
+    });
+}
+
+#[test]
+fn test_gradient_current_color_stop() {
+    use style::values::computed::image::compute_color_stops;
+    use style::values::generics::image::{ColorStop, GradientItem};
+
+    let device = test_device();
+    let blue = RGBA::new(0, 0, 255, 255);
+    ContextBuilder::new(&device).with_color(blue).build(|context| {
+        for s in &["linear-gradient(currentColor, red)", "linear-gradient(CURRENTCOLOR, red)"] {
+            let gradient = match parse_value::<Image>(s) {
+                GenericImage::Gradient(gradient) => gradient,
+                _ => panic!("{} is not a gradient", s),
+            };
+            let items = compute_color_stops(&gradient.items, context);
+            assert_eq!(items[0], GradientItem::ColorStop(ColorStop { color: blue, position: None }));
+        }
+
+        let image = parse_value::<Image>("linear-gradient(red, currentcolor 50%)").to_computed_value(context);
+        assert_eq!(image.to_css_string(),
+                   "linear-gradient(rgb(255, 0, 0), rgb(0, 0, 255) 50%)");
+    });
+}
+
+#[test]
+fn test_length_or_number_compute() {
+    use style::values::Either;
+    use style::values::specified::LengthOrNumber;
+
+    let device = test_device();
+    ContextBuilder::new(&device).with_font_size(Length::new(10.)).build(|context| {
+        let compute = |s: &str| parse_value::<LengthOrNumber>(s).to_computed_value(context);
+        assert_eq!(compute("2em"), Either::First(Length::new(20.)));
+        assert_eq!(compute("3"), Either::Second(3.));
+        assert_eq!(compute("0"), Either::First(Length::new(0.)));
+    });
+}
+
+#[test]
+fn test_viewport_units_resolve_against_current_viewport() {
+    use style::values::specified::Length as SpecifiedLength;
+
+    // Viewport units are resolved at compute time. The device records that
+    // they were used, so that styles get recomputed when the viewport changes.
+    let length = parse_value::<SpecifiedLength>("50vw");
+    let calc = parse_value::<LengthOrPercentage>("calc(10vh + 50%)");
+
+    let device = test_device();
+    assert!(!device.used_viewport_units());
+    ContextBuilder::new(&device).build(|context| {
+        assert_eq!(length.to_computed_value(context), Length::new(400.));
+        assert_eq!(calc.to_computed_value(context).to_used_value(Au::from_px(100)), Au::from_px(110));
+    });
+    assert!(device.used_viewport_units());
+
+    let resized = Device::new(MediaType::screen(), TypedSize2D::new(1000.0, 300.0), TypedScale::new(1.0));
+    ContextBuilder::new(&resized).build(|context| {
+        assert_eq!(length.to_computed_value(context), Length::new(500.));
+        assert_eq!(calc.to_computed_value(context).to_used_value(Au::from_px(100)), Au::from_px(80));
+    });
+}
+
+#[test]
+fn test_line_height_compute() {
+    use style::values::computed::NonNegativeNumber;
+    use style::values::generics::text::LineHeight as GenericLineHeight;
+    use style::values::specified::text::LineHeight;
+
+    let device = test_device();
+    ContextBuilder::new(&device).with_font_size(Length::new(10.)).build(|context| {
+        let compute = |s: &str| parse_value::<LineHeight>(s).to_computed_value(context);
+        assert_eq!(compute("normal"), GenericLineHeight::Normal);
+        // Numbers are kept as-is, so that they're inherited as numbers and
+        // resolved against the font size of each element.
+        assert_eq!(compute("1.5"), GenericLineHeight::Number(NonNegativeNumber::from(1.5)));
+        // Percentages are resolved against the font size.
+        assert_eq!(compute("150%"), GenericLineHeight::Length(Length::new(15.).into()));
+        assert_eq!(compute("20px"), GenericLineHeight::Length(Length::new(20.).into()));
+        assert_eq!(compute("calc(1em + 50%)"), GenericLineHeight::Length(Length::new(15.).into()));
+    });
+}
+
+#[test]
+fn test_length_cmp_computed() {
+    use std::cmp::Ordering;
+    use style::values::specified::Length as SpecifiedLength;
+
+    let device = test_device();
+    let em = parse_value::<SpecifiedLength>("1em");
+    let px = parse_value::<SpecifiedLength>("10px");
+
+    ContextBuilder::new(&device).with_font_size(Length::new(16.)).build(|context| {
+        assert_eq!(em.cmp_computed(&px, context), Ordering::Greater);
+        assert_eq!(px.cmp_computed(&em, context), Ordering::Less);
+    });
+    ContextBuilder::new(&device).with_font_size(Length::new(10.)).build(|context| {
+        assert_eq!(em.cmp_computed(&px, context), Ordering::Equal);
+    });
+    ContextBuilder::new(&device).with_font_size(Length::new(8.)).build(|context| {
+        assert_eq!(em.cmp_computed(&px, context), Ordering::Less);
+        let calc = parse_value::<SpecifiedLength>("calc(1em + 2px)");
+        assert_eq!(calc.cmp_computed(&px, context), Ordering::Equal);
+    });
+}
+
+#[test]
+fn test_min_max_compute() {
+    use style::values::specified::Length as SpecifiedLength;
+
+    let device = test_device();
+    ContextBuilder::new(&device).with_font_size(Length::new(16.)).build(|context| {
+        let compute = |s: &str| parse_value::<SpecifiedLength>(s).to_computed_value(context);
+
+        assert_eq!(compute("max(10px, 20px)"), Length::new(20.));
+        assert_eq!(compute("min(10px, 20px)"), Length::new(10.));
+        assert_eq!(compute("min(1em, 12px, 3vw)"), Length::new(12.));
+        assert_eq!(compute("max(1em, calc(10px + 1em))"), Length::new(26.));
+
+        // clamp(MIN, VAL, MAX), where 5vw is 40px.
+        assert_eq!(compute("clamp(1em, 5vw, 50px)"), Length::new(40.));
+        assert_eq!(compute("clamp(1em, 5vw, 20px)"), Length::new(20.));
+        assert_eq!(compute("clamp(1em, 1vw, 20px)"), Length::new(16.));
+        // The minimum wins if it's bigger than the maximum.
+        assert_eq!(compute("clamp(30px, 5vw, 20px)"), Length::new(30.));
+
+        // The percentage is kept for layout, and the lengths are compared.
+        let lop = parse_value::<LengthOrPercentage>("min(10% + 5px, 10% + 2em)").to_computed_value(context);
+        assert_eq!(lop, ComputedLengthOrPercentage::Calc(
+            CalcLengthOrPercentage::new(Length::new(5.), Some(Percentage(0.1)))));
+    });
+}
+
+#[test]
+fn test_min_max_with_percentages_resolve() {
+    use style::values::computed::LengthOrPercentageOrAuto as ComputedLengthOrPercentageOrAuto;
+    use style::values::specified::LengthOrPercentageOrAuto;
+
+    let device = test_device();
+    ContextBuilder::new(&device).with_font_size(Length::new(16.)).build(|context| {
+        let compute = |s: &str| parse_value::<LengthOrPercentage>(s).to_computed_value(context);
+        let used = |s: &str, basis: i32| compute(s).to_used_value(Au::from_px(basis));
+
+        // Arguments with different percentages are compared once the
+        // percentages are resolved.
+        assert_eq!(used("min(10px, 50%)", 100), Au::from_px(10));
+        assert_eq!(used("min(10px, 50%)", 10), Au::from_px(5));
+        assert_eq!(used("max(10px, 50%)", 100), Au::from_px(50));
+        assert_eq!(used("max(10% + 5px, 20% + 5px, 1em)", 50), Au::from_px(16));
+        assert_eq!(used("max(10% + 5px, 20% + 5px, 1em)", 200), Au::from_px(45));
+        assert_eq!(used("clamp(100px, 50%, 20% + 1em)", 100), Au::from_px(100));
+        assert_eq!(used("clamp(10px, 50%, 20% + 1em)", 100), Au::from_px(36));
+        assert_eq!(used("clamp(10px, 50%, 20% + 1em)", 10), Au::from_px(10));
+
+        // The arguments with the same percentage are compared right away.
+        assert_eq!(compute("max(10px, 1em, 10%)").to_css_string(), "max(16px, 10%)");
+        assert_eq!(compute("min(10px, 50% + 2em)").to_css_string(), "min(10px, 50% + 32px)");
+
+        let auto = parse_value::<LengthOrPercentageOrAuto>("min(100px, 50% + 10px)").to_computed_value(context);
+        assert_eq!(auto.to_used_value(Au::from_px(100)), Some(Au::from_px(60)));
+        assert_eq!(auto.to_used_value(Au::from_px(400)), Some(Au::from_px(100)));
+        assert!(match auto {
+            ComputedLengthOrPercentageOrAuto::Calc(..) => true,
+            _ => false,
+        });
+
+        // The specified value of a computed value keeps the comparison.
+        assert_eq!(compute("min(10px, 50%)").to_specified().to_css_string(), "min(10px, 50%)");
+    });
+}
+
+#[test]
+fn test_stepped_value_functions_compute() {
+    use style::values::specified::Length as SpecifiedLength;
+
+    let device = test_device();
+    ContextBuilder::new(&device).with_font_size(Length::new(16.)).build(|context| {
+        let compute = |s: &str| parse_value::<SpecifiedLength>(s).to_computed_value(context);
+
+        assert_eq!(compute("round(up, 23px, 10px)"), Length::new(30.));
+        assert_eq!(compute("round(down, 27px, 10px)"), Length::new(20.));
+        assert_eq!(compute("round(to-zero, 27px, 10px)"), Length::new(20.));
+        assert_eq!(compute("round(to-zero, -27px, 10px)"), Length::new(-20.));
+        assert_eq!(compute("round(23px, 10px)"), Length::new(20.));
+        // Ties round up.
+        assert_eq!(compute("round(25px, 10px)"), Length::new(30.));
+        assert_eq!(compute("round(-25px, 10px)"), Length::new(-20.));
+        // 1em + 1px is 17px, and 1vw is 8px.
+        assert_eq!(compute("round(up, 1em + 1px, 5px)"), Length::new(20.));
+        assert_eq!(compute("round(up, 1vw, 3px)"), Length::new(9.));
+
+        // mod() takes the sign of the step, and rem() the sign of the value.
+        assert_eq!(compute("mod(18px, 5px)"), Length::new(3.));
+        assert_eq!(compute("mod(-18px, 5px)"), Length::new(2.));
+        assert_eq!(compute("mod(18px, -5px)"), Length::new(-2.));
+        assert_eq!(compute("rem(18px, 5px)"), Length::new(3.));
+        assert_eq!(compute("rem(-18px, 5px)"), Length::new(-3.));
+
+        // A zero step would be NaN, which is clamped to zero.
+        assert_eq!(compute("mod(18px, 0px)"), Length::new(0.));
+        assert_eq!(compute("round(18px, 0px)"), Length::new(0.));
+
+        let lop = parse_value::<LengthOrPercentage>("mod(18px, 5px)").to_computed_value(context);
+        assert_eq!(lop, ComputedLengthOrPercentage::Calc(
+            CalcLengthOrPercentage::new(Length::new(3.), None)));
+    });
+}
+
+#[test]
+fn test_opacity_clamping() {
+    use style::values::specified::Opacity;
+
+    Context::for_testing(|context| {
+        let compute = |s: &str| parse_value::<Opacity>(s).to_computed_value(context);
+        assert_eq!(compute("0.5"), 0.5);
+        assert_eq!(compute("50%"), 0.5);
+        assert_eq!(compute("2"), 1.);
+        assert_eq!(compute("-0.5"), 0.);
+        assert_eq!(compute("150%"), 1.);
+        assert_eq!(compute("-50%"), 0.);
+    });
+
+    // SMIL animations interpolate between out-of-range values.
+    let device = test_device();
+    ContextBuilder::new(&device).with_smil_animation(true).build(|context| {
+        assert_eq!(parse_value::<Opacity>("2").to_computed_value(context), 2.);
+    });
+}
+
+#[test]
+fn test_number_or_percentage_fraction() {
+    use style::values::computed::NumberOrPercentage as ComputedNumberOrPercentage;
+    use style::values::specified::NumberOrPercentage;
+
+    Context::for_testing(|context| {
+        let compute = |s: &str| parse_value::<NumberOrPercentage>(s).to_computed_value(context);
+        assert_eq!(compute("0.5"), ComputedNumberOrPercentage::Number(0.5));
+        assert_eq!(compute("50%"), ComputedNumberOrPercentage::Percentage(Percentage(0.5)));
+        assert_eq!(compute("0.5").to_fraction(), 0.5);
+        assert_eq!(compute("50%").to_fraction(), 0.5);
+        assert_eq!(compute("150%").to_fraction(), 1.5);
+
+        assert_eq!(compute("150%").clamped_fraction(), 1.);
+        assert_eq!(compute("-2").clamped_fraction(), 0.);
+        assert_eq!(compute("-50%").clamp_to_non_negative(),
+                   ComputedNumberOrPercentage::Percentage(Percentage(0.)));
+        assert_eq!(compute("-2").clamp_to_non_negative(), ComputedNumberOrPercentage::Number(0.));
+    });
+}
+
+#[test]
+fn test_background_repeat_compute() {
+    use style::values::computed::BackgroundRepeat as ComputedBackgroundRepeat;
+    use style::values::specified::BackgroundRepeat;
+    use style::values::specified::background::BackgroundRepeatKeyword::*;
+
+    Context::for_testing(|context| {
+        let compute = |s: &str| parse_value::<BackgroundRepeat>(s).to_computed_value(context);
+        // A single keyword applies to both axes.
+        assert_eq!(compute("space"), ComputedBackgroundRepeat(Space, Space));
+        assert_eq!(compute("repeat no-repeat"), ComputedBackgroundRepeat(Repeat, NoRepeat));
+        assert_eq!(compute("repeat-x"), ComputedBackgroundRepeat(Repeat, NoRepeat));
+        assert_eq!(compute("repeat-y"), ComputedBackgroundRepeat(NoRepeat, Repeat));
+
+        // The computed value serializes in its shortest form.
+        assert_eq!(compute("round round").to_css_string(), "round");
+        assert_eq!(compute("repeat no-repeat").to_css_string(), "repeat-x");
+        assert_eq!(compute("space round").to_css_string(), "space round");
+
+        let computed = compute("no-repeat space");
+        assert_eq!(BackgroundRepeat::from_computed_value(&computed),
+                   parse_value::<BackgroundRepeat>("no-repeat space"));
+    });
+}
+
+#[test]
+fn test_constant_calc_computes_to_a_length() {
+    use style::values::specified::Length as SpecifiedLength;
+
+    Context::for_testing(|context| {
+        let length = parse_value::<SpecifiedLength>("calc(10px + 5px)").to_computed_value(context);
+        assert_eq!(length, Length::new(15.));
+        assert_eq!(length.to_css_string(), "15px");
+
+        let length = parse_value::<SpecifiedLength>("calc(10px + 1em)").to_computed_value(context);
+        assert_eq!(length, Length::new(26.));
+
+        // Negative results are clamped for properties that don't allow them.
+        let length = with_parser_context("http://localhost", |parser_context| {
+            let mut input = ::cssparser::ParserInput::new("calc(10px - 20px)");
+            let mut parser = ::cssparser::Parser::new(&mut input);
+            SpecifiedLength::parse_non_negative(parser_context, &mut parser).unwrap()
+        });
+        assert_eq!(length.to_computed_value(context), Length::new(0.));
+        let length = parse_value::<SpecifiedLength>("calc(10px - 20px)").to_computed_value(context);
+        assert_eq!(length, Length::new(-10.));
+    });
+}
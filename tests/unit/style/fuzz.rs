@@ -0,0 +1,109 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Feeds pseudo-random strings to the value parsers, and checks that parsing,
+//! computing and serializing whatever comes out of them doesn't panic.
+
+use servo_url::ServoUrl;
+use style::parser::{parse_value_safely, Parse};
+use style::values::computed::{Context, ToComputedValue};
+use style::values::specified::{Angle, BackgroundRepeat, Color, Integer, LengthOrPercentage};
+use style::values::specified::{LineHeight, Number, NumberOrPercentage, Percentage, Time};
+use style::values::specified::image::Image;
+use style::values::specified::length::Length;
+use style::values::specified::position::Position;
+use style_traits::ToCss;
+
+/// Fragments that are likely to reach interesting paths in the parsers.
+const FRAGMENTS: &'static [&'static str] = &[
+    "0", "1", "-1", "1.5", "1e38", "-1e38", "3.4e38", "1e-38", "+", "-", "*", "/", " ", ",",
+    "(", ")", "%", "px", "em", "rem", "ex", "ch", "vw", "vh", "vmin", "vmax", "in", "cm",
+    "mm", "q", "pt", "pc", "deg", "rad", "grad", "turn", "s", "ms", "calc(", "min(",
+    "max(", "clamp(", "round(", "mod(", "rem(", "up", "to-zero", "linear-gradient(", "radial-gradient(", "conic-gradient(",
+    "-webkit-linear-gradient(", "-moz-linear-gradient(", "repeating-linear-gradient(",
+    "cross-fade(", "image-set(", "image(", "attr(", "url(", "to ", "left", "top",
+    "right", "bottom", "center", "at ", "from ", "in ", "srgb", "oklch", "longer", "hue",
+    "circle", "ellipse", "closest-side", "farthest-corner", "red", "transparent",
+    "currentcolor", "#f00", "#ff000080", "rgb(", "rgba(", "hsl(", "repeat", "no-repeat",
+    "space", "round", "repeat-x", "normal", "auto", "none", "\"", "\\", "x", "dppx",
+];
+
+/// A xorshift pseudo-random number generator, so that failures can be
+/// reproduced.
+struct Rng(u32);
+
+impl Rng {
+    fn next(&mut self) -> u32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        self.0
+    }
+
+    fn string(&mut self) -> String {
+        let len = self.next() % 12;
+        let mut s = String::new();
+        for _ in 0..len {
+            let index = self.next() as usize % FRAGMENTS.len();
+            s.push_str(FRAGMENTS[index]);
+        }
+        s
+    }
+}
+
+fn exercise<T>(context: &Context, url: &ServoUrl, input: &str)
+where
+    T: Parse + ToCss + ToComputedValue,
+    T::ComputedValue: ToCss,
+{
+    if let Ok(value) = parse_value_safely::<T>(url, input) {
+        value.to_css_string();
+        value.to_computed_value(context).to_css_string();
+    }
+}
+
+#[test]
+fn test_parsing_random_input_does_not_panic() {
+    let url = ServoUrl::parse("http://localhost").unwrap();
+    let mut rng = Rng(0x9e3779b9);
+
+    Context::for_testing(|context| {
+        for _ in 0..5000 {
+            let input = rng.string();
+            exercise::<Angle>(context, &url, &input);
+            exercise::<BackgroundRepeat>(context, &url, &input);
+            exercise::<Color>(context, &url, &input);
+            exercise::<Image>(context, &url, &input);
+            exercise::<Integer>(context, &url, &input);
+            exercise::<Length>(context, &url, &input);
+            exercise::<LengthOrPercentage>(context, &url, &input);
+            exercise::<LineHeight>(context, &url, &input);
+            exercise::<Number>(context, &url, &input);
+            exercise::<NumberOrPercentage>(context, &url, &input);
+            exercise::<Percentage>(context, &url, &input);
+            exercise::<Position>(context, &url, &input);
+            exercise::<Time>(context, &url, &input);
+        }
+    });
+}
+
+#[test]
+fn test_overflowing_calc_is_finite() {
+    let url = ServoUrl::parse("http://localhost").unwrap();
+
+    Context::for_testing(|context| {
+        let lop = parse_value_safely::<LengthOrPercentage>(&url, "calc(3e38px * 10 - 3e38px * 10)").unwrap();
+        let computed = lop.to_computed_value(context);
+        assert_eq!(computed.to_css_string(), "0px");
+
+        let lop = parse_value_safely::<LengthOrPercentage>(&url, "calc(3e38% * 10 + 1px)").unwrap();
+        lop.to_css_string();
+        lop.to_computed_value(context).to_css_string();
+
+        let integer = parse_value_safely::<Integer>(&url, "calc(3e38 * 10)").unwrap();
+        assert_eq!(integer.to_computed_value(context), i32::max_value());
+        let integer = parse_value_safely::<Integer>(&url, "calc(-3e38 * 10)").unwrap();
+        assert_eq!(integer.to_computed_value(context), i32::min_value());
+    });
+}
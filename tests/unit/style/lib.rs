@@ -24,18 +24,22 @@ extern crate test;
 
 mod animated_properties;
 mod attr;
+mod computed_values;
 mod custom_properties;
+mod fuzz;
 mod keyframes;
 mod logical_geometry;
 mod media_queries;
 mod parsing;
 mod properties;
 mod rule_tree;
+mod serde_values;
 mod size_of;
 mod specified_values;
 mod str;
 mod stylesheets;
 mod stylist;
+mod units;
 mod viewport;
 
 mod writing_modes {
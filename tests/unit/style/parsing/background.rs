@@ -2,11 +2,15 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use parsing::parse;
+use parsing::{parse, parse_entirely};
+use style::parser::Parse;
 use style::properties::longhands::{background_attachment, background_clip, background_color, background_image};
 use style::properties::longhands::{background_origin, background_position_x, background_position_y, background_repeat};
 use style::properties::longhands::background_size;
 use style::properties::shorthands::background;
+use style::values::generics::background::BackgroundSize as GenericBackgroundSize;
+use style::values::specified::BackgroundSize;
+use style_traits::ToCss;
 
 #[test]
 fn background_shorthand_should_parse_all_available_properties_when_specified() {
@@ -102,3 +106,76 @@ fn background_shorthand_should_parse_origin_and_clip_correctly() {
     assert_eq!(result.background_origin, parse_longhand!(background_origin, "padding-box"));
     assert_eq!(result.background_clip, parse_longhand!(background_clip, "padding-box"));
 }
+
+#[test]
+fn background_size_should_parse_keywords_and_explicit_sizes() {
+    assert_roundtrip_with_context!(BackgroundSize::parse, "cover");
+    assert_roundtrip_with_context!(BackgroundSize::parse, "contain");
+    assert_roundtrip_with_context!(BackgroundSize::parse, "100% auto");
+    assert_roundtrip_with_context!(BackgroundSize::parse, "10px 20%");
+    assert_roundtrip_with_context!(BackgroundSize::parse, "auto", "auto auto");
+
+    // A single value leaves the height as `auto`.
+    assert_roundtrip_with_context!(BackgroundSize::parse, "50%", "50% auto");
+    assert_eq!(parse(BackgroundSize::parse, "50%").unwrap(),
+               parse(BackgroundSize::parse, "50% auto").unwrap());
+    match parse(BackgroundSize::parse, "auto").unwrap() {
+        GenericBackgroundSize::Explicit { .. } => {},
+        other => panic!("Unexpected background-size {:?}", other),
+    }
+
+    assert!(parse(BackgroundSize::parse, "-10px").is_err());
+    assert!(parse_entirely(BackgroundSize::parse, "cover contain").is_err());
+    assert!(parse_entirely(BackgroundSize::parse, "10px 20px 30px").is_err());
+}
+
+#[test]
+fn background_image_should_parse_comma_separated_images() {
+    use style::values::Either;
+    use style::values::generics::image::Image as GenericImage;
+
+    let input = "url(\"http://servo/a.png\"), linear-gradient(red, blue), none";
+    assert_roundtrip_with_context!(background_image::parse, input);
+
+    let layers = parse_entirely(background_image::parse, input).unwrap().0;
+    assert_eq!(layers.len(), 3);
+    match layers[0] {
+        Either::Second(GenericImage::Url(..)) => {},
+        _ => panic!("Expected an url image"),
+    }
+    match layers[1] {
+        Either::Second(GenericImage::Gradient(..)) => {},
+        _ => panic!("Expected a gradient"),
+    }
+    match layers[2] {
+        Either::First(..) => {},
+        _ => panic!("Expected none"),
+    }
+
+    assert!(parse_entirely(background_image::parse, "none, ").is_err());
+    assert!(parse_entirely(background_image::parse, ", none").is_err());
+    assert!(parse_entirely(background_image::parse, "none none").is_err());
+}
+
+#[test]
+fn test_background_repeat() {
+    use style::values::specified::BackgroundRepeat;
+    use style::values::specified::background::BackgroundRepeatKeyword;
+
+    assert_eq!(parse_entirely(BackgroundRepeat::parse, "space").unwrap(),
+               BackgroundRepeat::Keywords(BackgroundRepeatKeyword::Space, None));
+    assert_eq!(parse_entirely(BackgroundRepeat::parse, "repeat no-repeat").unwrap(),
+               BackgroundRepeat::Keywords(BackgroundRepeatKeyword::Repeat,
+                                          Some(BackgroundRepeatKeyword::NoRepeat)));
+    assert_eq!(parse_entirely(BackgroundRepeat::parse, "repeat-x").unwrap(), BackgroundRepeat::RepeatX);
+
+    assert_roundtrip_with_context!(BackgroundRepeat::parse, "round");
+    assert_roundtrip_with_context!(BackgroundRepeat::parse, "repeat no-repeat");
+    assert_roundtrip_with_context!(BackgroundRepeat::parse, "space round");
+    assert_roundtrip_with_context!(BackgroundRepeat::parse, "repeat-y");
+
+    // The two-value syntax doesn't accept the single-axis keywords.
+    assert!(parse_entirely(BackgroundRepeat::parse, "repeat-x repeat").is_err());
+    assert!(parse_entirely(BackgroundRepeat::parse, "repeat repeat-x").is_err());
+    assert!(parse_entirely(BackgroundRepeat::parse, "repeat space round").is_err());
+}
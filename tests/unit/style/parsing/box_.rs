@@ -20,3 +20,22 @@ fn test_unexhausted_transform() {
     assert_parser_exhausted!(transform::parse, "rotate(70deg)foo", false);
     assert_parser_exhausted!(transform::parse, "rotate(70deg) foo", false);
 }
+
+#[test]
+fn test_aspect_ratio() {
+    use style::parser::Parse;
+    use style::values::specified::AspectRatio;
+
+    assert_roundtrip_with_context!(AspectRatio::parse, "16 / 9");
+    assert_roundtrip_with_context!(AspectRatio::parse, "16/9", "16 / 9");
+    assert_roundtrip_with_context!(AspectRatio::parse, "1.5", "1.5 / 1");
+    assert_roundtrip_with_context!(AspectRatio::parse, "auto");
+    assert_roundtrip_with_context!(AspectRatio::parse, "auto 16/9", "auto 16 / 9");
+    assert_roundtrip_with_context!(AspectRatio::parse, "16/9 auto", "auto 16 / 9");
+
+    assert!(parse(AspectRatio::parse, "-1").is_err());
+    assert!(parse(AspectRatio::parse, "16 / -9").is_err());
+    assert!(parse(AspectRatio::parse, "16 /").is_err());
+    assert!(parse(AspectRatio::parse, "auto auto").is_err());
+    assert!(parse(AspectRatio::parse, "").is_err());
+}
@@ -0,0 +1,143 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use cssparser::RGBA;
+use parsing::parse;
+use style::parser::Parse;
+use style::values::computed::Color as ComputedColor;
+use style::values::specified::{Color, RGBAColor};
+use style_traits::ToCss;
+
+#[test]
+fn test_rgba_color() {
+    assert_roundtrip_with_context!(RGBAColor::parse, "#ff0000");
+    assert_roundtrip_with_context!(RGBAColor::parse, "rgba(0,0,0,0.5)");
+    assert_roundtrip_with_context!(RGBAColor::parse, "Red", "red");
+
+    let parsed = parse(RGBAColor::parse, "rgba(0,0,0,0.5)").unwrap();
+    match parsed.0 {
+        Color::Numeric { parsed, .. } => assert_eq!(parsed, RGBA::new(0, 0, 0, 128)),
+        other => panic!("Unexpected color {:?}", other),
+    }
+
+    let parsed = parse(RGBAColor::parse, "Red").unwrap();
+    match parsed.0 {
+        Color::Numeric { parsed, authored: Some(ref authored) } => {
+            assert_eq!(parsed, RGBA::new(255, 0, 0, 255));
+            assert_eq!(&**authored, "red");
+        },
+        other => panic!("Unexpected color {:?}", other),
+    }
+
+    assert!(parse(RGBAColor::parse, "notacolor").is_err());
+}
+
+#[test]
+fn test_rgba_color_keywords_and_alpha() {
+    fn rgba(input: &'static str) -> (RGBA, String) {
+        let color = parse(RGBAColor::parse, input).unwrap();
+        match color.0 {
+            Color::Numeric { parsed, .. } => (parsed, color.to_css_string()),
+            other => panic!("Unexpected color {:?}", other),
+        }
+    }
+
+    assert_eq!(rgba("transparent"), (RGBA::new(0, 0, 0, 0), "transparent".to_owned()));
+    assert_eq!(rgba("red"), (RGBA::new(255, 0, 0, 255), "red".to_owned()));
+    assert_eq!(rgba("RebeccaPurple"), (RGBA::new(102, 51, 153, 255), "rebeccapurple".to_owned()));
+    assert_eq!(rgba("rgba(0,0,0,0.25)"), (RGBA::new(0, 0, 0, 64), "rgba(0,0,0,0.25)".to_owned()));
+}
+
+#[test]
+fn test_authored_color() {
+    // Functional notations keep the author's casing and spacing.
+    assert_roundtrip_with_context!(Color::parse, "RGB(255,  0, 0)");
+    assert_roundtrip_with_context!(Color::parse, "rgb(255, 0, 0)");
+    assert_roundtrip_with_context!(Color::parse, "hsl(120, 100%, 50%)");
+
+    // Hex colors are kept verbatim.
+    assert_roundtrip_with_context!(Color::parse, "#FF0000");
+    assert_roundtrip_with_context!(Color::parse, "#f00");
+
+    // Keywords are serialized in lowercase.
+    assert_roundtrip_with_context!(Color::parse, "ReD", "red");
+
+    // Leading whitespace and comments aren't part of the authored text.
+    assert_roundtrip_with_context!(Color::parse, "/*x*/ Red", "red");
+    assert_roundtrip_with_context!(Color::parse, " /*x*/ #F00", "#F00");
+    assert_roundtrip_with_context!(Color::parse, "/*x*/rgb(255, 0, 0)", "rgb(255, 0, 0)");
+}
+
+#[test]
+fn test_color_equality_ignores_authored_text() {
+    let red = parse(Color::parse, "red").unwrap();
+    assert_eq!(red, parse(Color::parse, "#ff0000").unwrap());
+    assert_eq!(red, parse(Color::parse, "RGB(255, 0, 0)").unwrap());
+    assert_eq!(red, Color::rgba(RGBA::new(255, 0, 0, 255)));
+    assert!(red != parse(Color::parse, "blue").unwrap());
+    assert!(red != Color::currentcolor());
+
+    assert_eq!(parse(RGBAColor::parse, "transparent").unwrap(), RGBAColor(Color::transparent()));
+    assert_eq!(parse(RGBAColor::parse, "red").unwrap(), parse(RGBAColor::parse, "#f00").unwrap());
+}
+
+#[test]
+fn test_hex_color_with_alpha() {
+    assert_roundtrip_with_context!(Color::parse, "#f00f");
+    assert_roundtrip_with_context!(Color::parse, "#ff0000ff");
+    assert_roundtrip_with_context!(Color::parse, "#FF000080");
+
+    assert_eq!(parse(Color::parse, "#f00f").unwrap(), Color::rgba(RGBA::new(255, 0, 0, 255)));
+    assert_eq!(parse(Color::parse, "#ff0000ff").unwrap(), Color::rgba(RGBA::new(255, 0, 0, 255)));
+    assert_eq!(parse(Color::parse, "#f008").unwrap(), Color::rgba(RGBA::new(255, 0, 0, 0x88)));
+    assert_eq!(parse(Color::parse, "#00ff0080").unwrap(), Color::rgba(RGBA::new(0, 255, 0, 0x80)));
+
+    assert!(parse(Color::parse, "#ff00f").is_err());
+    assert!(parse(Color::parse, "#ff0000f").is_err());
+}
+
+#[test]
+fn test_transparent_color() {
+    assert_roundtrip_with_context!(Color::parse, "transparent");
+    assert_roundtrip_with_context!(Color::parse, "Transparent", "transparent");
+    assert_roundtrip_with_context!(RGBAColor::parse, "transparent");
+
+    match parse(Color::parse, "transparent").unwrap() {
+        Color::Numeric { parsed, authored: Some(ref authored) } => {
+            assert_eq!(parsed, RGBA::transparent());
+            assert_eq!(&**authored, "transparent");
+        },
+        other => panic!("Unexpected color {:?}", other),
+    }
+}
+
+#[test]
+fn test_space_separated_color_functions_with_slash_alpha() {
+    fn rgba(input: &'static str) -> RGBA {
+        match parse(Color::parse, input).unwrap() {
+            Color::Numeric { parsed, .. } => parsed,
+            other => panic!("Unexpected color {:?}", other),
+        }
+    }
+
+    assert_eq!(rgba("rgb(255 0 0 / 50%)"), RGBA::new(255, 0, 0, 128));
+    assert_eq!(rgba("rgb(255 0 0 / 0.5)"), RGBA::new(255, 0, 0, 128));
+    assert_eq!(rgba("rgba(0 0 255 / 1)"), RGBA::new(0, 0, 255, 255));
+    assert_eq!(rgba("rgb(100% 0% 0%)"), RGBA::new(255, 0, 0, 255));
+    assert_eq!(rgba("hsl(120 50% 50% / 0.3)"), rgba("hsla(120, 50%, 50%, 0.3)"));
+    assert_eq!(rgba("hsl(120deg 100% 25% / 100%)"), rgba("hsl(120, 100%, 25%)"));
+
+    // The specified value keeps the authored text, while the computed value
+    // uses the legacy comma-separated form.
+    assert_roundtrip_with_context!(Color::parse, "rgb(255 0 0 / 50%)");
+    assert_roundtrip_with_context!(Color::parse, "rgb(255 0 0)");
+    assert_eq!(ComputedColor::rgba(rgba("rgb(255 0 0 / 50%)")).to_css_string(),
+               "rgba(255, 0, 0, 0.5)");
+    assert_eq!(ComputedColor::rgba(rgba("rgb(255 0 0)")).to_css_string(), "rgb(255, 0, 0)");
+
+    // Commas and slashes can't be mixed.
+    assert!(parse(Color::parse, "rgb(255, 0, 0 / 50%)").is_err());
+    assert!(parse(Color::parse, "rgb(255 0 0, 50%)").is_err());
+    assert!(parse(Color::parse, "hsl(120 50% 50%, 0.3)").is_err());
+}
@@ -89,3 +89,20 @@ fn blur_radius_should_not_accept_negavite_values() {
     assert!(parse(box_shadow::parse, "1px 1px 0").is_ok());// for zero
     assert!(parse(box_shadow::parse, "1px 1px 1px").is_ok());// for +ve value
 }
+
+#[test]
+fn test_opacity() {
+    use style::parser::Parse;
+    use style::values::specified::Opacity;
+
+    assert_roundtrip_with_context!(Opacity::parse, "0.5");
+    assert_roundtrip_with_context!(Opacity::parse, "50%", "0.5");
+    assert_roundtrip_with_context!(Opacity::parse, "calc(50%)", "calc(0.5)");
+    // Out of range values are preserved until computed value time.
+    assert_roundtrip_with_context!(Opacity::parse, "2");
+    assert_roundtrip_with_context!(Opacity::parse, "-0.5");
+    assert_roundtrip_with_context!(Opacity::parse, "150%", "1.5");
+
+    assert!(parse(Opacity::parse, "0.5px").is_err());
+    assert!(parse(Opacity::parse, "none").is_err());
+}
@@ -0,0 +1,33 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use parsing::parse_entirely;
+use style::parser::Parse;
+use style::values::specified::Frequency;
+use style_traits::ToCss;
+
+#[test]
+fn test_frequency() {
+    assert_roundtrip_with_context!(Frequency::parse, "440Hz");
+    assert_roundtrip_with_context!(Frequency::parse, "440hz", "440Hz");
+    assert_roundtrip_with_context!(Frequency::parse, "1kHz", "1000Hz");
+    assert_roundtrip_with_context!(Frequency::parse, "-10Hz");
+
+    assert_eq!(parse_entirely(Frequency::parse, "1kHz").unwrap(),
+               parse_entirely(Frequency::parse, "1000Hz").unwrap());
+    assert_eq!(parse_entirely(Frequency::parse, "2.5KHZ").unwrap().hz(), 2500.);
+
+    // Frequencies need a unit, even when zero.
+    assert!(parse_entirely(Frequency::parse, "0").is_err());
+    assert!(parse_entirely(Frequency::parse, "440").is_err());
+    assert!(parse_entirely(Frequency::parse, "440px").is_err());
+}
+
+#[test]
+fn test_non_negative_frequency() {
+    assert!(parse_entirely(Frequency::parse_non_negative, "0Hz").is_ok());
+    assert!(parse_entirely(Frequency::parse_non_negative, "1kHz").is_ok());
+    assert!(parse_entirely(Frequency::parse_non_negative, "-1Hz").is_err());
+    assert!(parse_entirely(Frequency::parse_non_negative, "-1kHz").is_err());
+}
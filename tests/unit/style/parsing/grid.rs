@@ -0,0 +1,26 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use parsing::parse_entirely;
+use style::parser::Parse;
+use style::values::generics::grid::TrackBreadth as GenericTrackBreadth;
+use style::values::specified::{TrackBreadth, TrackSize};
+use style_traits::ToCss;
+
+#[test]
+fn test_flex_track_breadth() {
+    assert_roundtrip_with_context!(TrackBreadth::parse, "2fr");
+    assert_roundtrip_with_context!(TrackBreadth::parse, "0.5fr");
+    assert_roundtrip_with_context!(TrackBreadth::parse, "1FR", "1fr");
+    assert_roundtrip_with_context!(TrackSize::parse, "minmax(100px, 1fr)");
+
+    assert_eq!(parse_entirely(TrackBreadth::parse, "2fr").unwrap(), GenericTrackBreadth::Fr(2.));
+
+    // Flex factors are non-negative, and are not lengths.
+    assert!(parse_entirely(TrackBreadth::parse, "-1fr").is_err());
+    assert!(parse_entirely(TrackBreadth::parse, "2").is_err());
+    // Flex factors can't be used as the minimum of a track.
+    assert!(parse_entirely(TrackSize::parse, "minmax(1fr, 100px)").is_err());
+    assert!(parse_entirely(TrackSize::parse, "fit-content(1fr)").is_err());
+}
@@ -2,9 +2,14 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use cssparser::ParseErrorKind;
+use parsing::{parse_entirely, parse_input};
 use style::parser::Parse;
+use style::values::Either;
+use style::values::generics::image::{GradientItem as GenericGradientItem, Image as GenericImage};
+use style::values::specified::Angle;
 use style::values::specified::image::*;
-use style_traits::ToCss;
+use style_traits::{StyleParseErrorKind, ToCss, ValueParseErrorKind};
 
 #[test]
 fn test_linear_gradient() {
@@ -20,6 +25,16 @@ fn test_linear_gradient() {
     // Parsing with <angle>
     assert_roundtrip_with_context!(Image::parse, "linear-gradient(45deg, red, green)");
 
+    // Parsing with <angle> keeps the authored unit
+    assert_roundtrip_with_context!(Image::parse, "linear-gradient(0.25turn, red, green)");
+    assert_roundtrip_with_context!(Image::parse, "linear-gradient(100grad, red, green)");
+    assert_roundtrip_with_context!(Image::parse, "linear-gradient(1rad, red, green)");
+
+    // Gradients accept a unitless zero angle, but no other unitless number
+    assert_roundtrip_with_context!(Image::parse, "linear-gradient(0, red, green)",
+                                                 "linear-gradient(0deg, red, green)");
+    assert!(parse_entirely(Image::parse, "linear-gradient(45, red, green)").is_err());
+
     // Parsing with more than two entries in <color-stop-list>
     assert_roundtrip_with_context!(Image::parse, "linear-gradient(red, yellow, green)");
 
@@ -28,6 +43,104 @@ fn test_linear_gradient() {
 
     // Parsing without <angle> and <side-or-corner>
     assert_roundtrip_with_context!(Image::parse, "linear-gradient(red, green)");
+
+    // The default direction is omitted when serializing
+    assert_roundtrip_with_context!(Image::parse, "linear-gradient(to bottom, red, green)",
+                                                 "linear-gradient(red, green)");
+    assert_roundtrip_with_context!(Image::parse, "-webkit-linear-gradient(top, red, green)",
+                                                 "-webkit-linear-gradient(red, green)");
+
+    // Parsing with two positions for a color stop
+    assert_roundtrip_with_context!(Image::parse, "linear-gradient(red 0% 50%, blue)",
+                                                 "linear-gradient(red 0%, red 50%, blue)");
+    assert_roundtrip_with_context!(Image::parse, "linear-gradient(red, green 10px 20px, blue 50%)",
+                                                 "linear-gradient(red, green 10px, green 20px, blue 50%)");
+
+    // Parsing with interpolation hints between color stops
+    assert_roundtrip_with_context!(Image::parse, "linear-gradient(red, 30%, blue)");
+    assert_roundtrip_with_context!(Image::parse, "linear-gradient(red 10%, 20px, blue 50%)");
+    assert!(parse_entirely(Image::parse, "linear-gradient(30%, red, blue)").is_err());
+    assert!(parse_entirely(Image::parse, "linear-gradient(red, 30%, 40%, blue)").is_err());
+    assert!(parse_entirely(Image::parse, "linear-gradient(red, blue, 30%)").is_err());
+}
+
+#[test]
+fn test_linear_gradient_corners() {
+    // Both keyword orders produce the same corner.
+    let top_left = parse_entirely(Image::parse, "linear-gradient(to top left, red, blue)").unwrap();
+    let left_top = parse_entirely(Image::parse, "linear-gradient(to left top, red, blue)").unwrap();
+    assert_eq!(top_left, left_top);
+    assert_eq!(top_left.to_css_string(), "linear-gradient(to left top, red, blue)");
+
+    // Each axis can only be given once.
+    for &input in ["linear-gradient(to top top, red, blue)",
+                   "linear-gradient(to bottom top, red, blue)",
+                   "linear-gradient(to left right, red, blue)",
+                   "linear-gradient(to left left, red, blue)",
+                   "linear-gradient(to top left top, red, blue)"].iter() {
+        assert!(parse_entirely(Image::parse, input).is_err(), "{} should not parse", input);
+    }
+}
+
+#[test]
+fn test_linear_gradient_corner_serialization() {
+    // Corners serialize horizontal keyword first, as browsers do, whatever
+    // order they were specified in.
+    for &(vertical_first, canonical) in [
+        ("linear-gradient(to top left, red, blue)", "linear-gradient(to left top, red, blue)"),
+        ("linear-gradient(to top right, red, blue)", "linear-gradient(to right top, red, blue)"),
+        ("linear-gradient(to bottom left, red, blue)", "linear-gradient(to left bottom, red, blue)"),
+        ("linear-gradient(to bottom right, red, blue)", "linear-gradient(to right bottom, red, blue)"),
+    ].iter() {
+        assert_roundtrip_with_context!(Image::parse, vertical_first, canonical);
+        assert_roundtrip_with_context!(Image::parse, canonical);
+    }
+
+    // The legacy syntax keeps the same order, without the `to` prefix.
+    assert_roundtrip_with_context!(Image::parse,
+                                   "-webkit-linear-gradient(top left, red, blue)",
+                                   "-webkit-linear-gradient(left top, red, blue)");
+}
+
+#[test]
+fn test_gradient_color_interpolation() {
+    assert_roundtrip_with_context!(Image::parse, "linear-gradient(in srgb, red, blue)");
+    assert_roundtrip_with_context!(Image::parse, "linear-gradient(in oklch longer hue, red, blue)");
+    assert_roundtrip_with_context!(Image::parse, "linear-gradient(to right in display-p3, red, blue)");
+    assert_roundtrip_with_context!(Image::parse,
+                                   "linear-gradient(in xyz-d50 45deg, red, blue)",
+                                   "linear-gradient(45deg in xyz-d50, red, blue)");
+    assert_roundtrip_with_context!(Image::parse, "radial-gradient(in hsl increasing hue, red, blue)");
+    assert_roundtrip_with_context!(Image::parse,
+                                   "radial-gradient(circle at center in srgb-linear, red, blue)");
+    assert_roundtrip_with_context!(Image::parse, "repeating-linear-gradient(in lab, red, blue)");
+
+    // Hue interpolation methods only apply to polar color spaces.
+    assert!(parse_entirely(Image::parse, "linear-gradient(in srgb longer hue, red, blue)").is_err());
+    assert!(parse_entirely(Image::parse, "linear-gradient(in oklch longer, red, blue)").is_err());
+    assert!(parse_entirely(Image::parse, "linear-gradient(in foo, red, blue)").is_err());
+    assert!(parse_entirely(Image::parse, "linear-gradient(in srgb in srgb, red, blue)").is_err());
+    assert!(parse_entirely(Image::parse, "linear-gradient(in srgb red, blue)").is_err());
+    // Prefixed gradients don't accept an interpolation method.
+    assert!(parse_entirely(Image::parse, "-webkit-linear-gradient(in srgb, red, blue)").is_err());
+}
+
+#[test]
+fn test_gradient_with_too_few_stops() {
+    let too_few_stops = ParseErrorKind::Custom(
+        StyleParseErrorKind::ValueError(ValueParseErrorKind::TooFewGradientStops),
+    );
+    for &input in ["linear-gradient(red)", "linear-gradient(to left, red)",
+                   "radial-gradient(circle, red)", "repeating-linear-gradient(red 10px)",
+                   "linear-gradient(red 0% 50%)"].iter() {
+        let error = parse_entirely(Gradient::parse, input).unwrap_err();
+        assert_eq!(error.kind, too_few_stops, "{}", input);
+    }
+
+    assert!(parse_entirely(Gradient::parse, "linear-gradient(red, blue)").is_ok());
+    // A trailing interpolation hint is a different error.
+    let error = parse_entirely(Gradient::parse, "linear-gradient(red, 50%)").unwrap_err();
+    assert!(error.kind != too_few_stops);
 }
 
 #[test]
@@ -102,3 +215,292 @@ fn test_radial_gradient() {
                                    "repeating-radial-gradient(red, green)",
                                    "repeating-radial-gradient(at center center, red, green)");
 }
+
+#[test]
+fn test_repeating_radial_gradient() {
+    assert_roundtrip_with_context!(Image::parse,
+                                   "repeating-radial-gradient(circle, red, blue 20px)",
+                                   "repeating-radial-gradient(circle at center center, red, blue 20px)");
+    assert_roundtrip_with_context!(Image::parse,
+                                   "repeating-radial-gradient(closest-side at 20px 30px, red, blue 20px)");
+
+    // The repeating form goes through the same parser as radial-gradient(),
+    // and only differs by its flag.
+    let repeating = parse_entirely(Image::parse, "repeating-radial-gradient(circle, red, blue 20px)");
+    let plain = parse_entirely(Image::parse, "radial-gradient(circle, red, blue 20px)");
+    match (repeating.unwrap(), plain.unwrap()) {
+        (GenericImage::Gradient(repeating), GenericImage::Gradient(plain)) => {
+            assert!(repeating.repeating);
+            assert!(!plain.repeating);
+            assert_eq!(repeating.kind, plain.kind);
+            assert_eq!(repeating.items, plain.items);
+        },
+        _ => panic!("Expected gradients"),
+    }
+
+    assert!(parse_entirely(Image::parse, "repeating-radial-gradient(circle, red)").is_err());
+}
+
+#[test]
+fn test_radial_gradient_position() {
+    assert_roundtrip_with_context!(Image::parse,
+                                   "radial-gradient(circle at center, red, blue)",
+                                   "radial-gradient(circle at center center, red, blue)");
+    assert_roundtrip_with_context!(Image::parse, "radial-gradient(circle at 30% 70%, red, blue)");
+
+    // Keywords with edge offsets, in either order.
+    assert_roundtrip_with_context!(Image::parse, "radial-gradient(circle at right 20px bottom, red, blue)");
+    assert_roundtrip_with_context!(Image::parse,
+                                   "radial-gradient(circle at bottom right 20px, red, blue)",
+                                   "radial-gradient(circle at right 20px bottom, red, blue)");
+    assert_roundtrip_with_context!(Image::parse,
+                                   "radial-gradient(circle at right 20px bottom 10%, red, blue)");
+
+    assert!(parse_entirely(Image::parse, "radial-gradient(circle at, red, blue)").is_err());
+    assert!(parse_entirely(Image::parse, "radial-gradient(circle at right left, red, blue)").is_err());
+}
+
+#[test]
+fn test_conic_gradient() {
+    // Parsing with just color stops
+    assert_roundtrip_with_context!(Image::parse, "conic-gradient(red, blue)");
+    assert_roundtrip_with_context!(Image::parse, "conic-gradient(red 0deg, yellow 25%, blue 1turn)");
+    assert_roundtrip_with_context!(Image::parse, "conic-gradient(red, 45deg, blue)");
+
+    // Angular stop positions keep their authored unit
+    assert_roundtrip_with_context!(Image::parse, "conic-gradient(red 0deg, blue 180deg)");
+    assert_roundtrip_with_context!(Image::parse, "conic-gradient(red 0.25turn, blue 200grad, green 4rad)");
+
+    // Parsing with a starting angle and a center
+    assert_roundtrip_with_context!(Image::parse, "conic-gradient(from 45deg, red, blue)");
+    assert_roundtrip_with_context!(Image::parse, "conic-gradient(at 20px 30px, red, blue)");
+    assert_roundtrip_with_context!(Image::parse,
+                                   "conic-gradient(from 45deg at center, red, blue)",
+                                   "conic-gradient(from 45deg at center center, red, blue)");
+    assert_roundtrip_with_context!(Image::parse,
+                                   "conic-gradient(from 0 at left top, red, blue)",
+                                   "conic-gradient(from 0deg at left top, red, blue)");
+
+    // Parsing repeating conic gradient
+    assert_roundtrip_with_context!(Image::parse, "repeating-conic-gradient(red, blue 30deg)");
+
+    match parse_entirely(Image::parse, "conic-gradient(from 90deg, red, blue)").unwrap() {
+        GenericImage::ConicGradient(ref gradient) => {
+            assert_eq!(gradient.angle, Some(Angle::from_degrees(90., false)));
+            assert!(gradient.position.is_none());
+            assert_eq!(gradient.items.len(), 2);
+            assert!(!gradient.repeating);
+        },
+        _ => panic!("Expected a conic gradient"),
+    }
+
+    // Too few color stops
+    assert!(parse_entirely(Image::parse, "conic-gradient(red)").is_err());
+    assert!(parse_entirely(Image::parse, "conic-gradient(from 45deg, red)").is_err());
+    assert!(parse_entirely(Image::parse, "conic-gradient(red, 45deg)").is_err());
+
+    // Invalid prefixes
+    assert!(parse_entirely(Image::parse, "conic-gradient(from 45deg red, blue)").is_err());
+    assert!(parse_entirely(Image::parse, "conic-gradient(from 10px, red, blue)").is_err());
+    assert!(parse_entirely(Image::parse, "conic-gradient(red 10px, blue)").is_err());
+    assert!(parse_entirely(Image::parse, "-webkit-conic-gradient(red, blue)").is_err());
+}
+
+#[test]
+fn test_conic_gradient_calc_angles() {
+    let angle = parse_entirely(Angle::parse, "calc(0.25turn + 10deg)").unwrap();
+    assert!((angle.degrees() - 100.).abs() < 1e-4);
+
+    let gradient = "conic-gradient(from calc(1turn - 90deg), red calc(0.25turn + 10deg), blue)";
+    match parse_entirely(Image::parse, gradient).unwrap() {
+        GenericImage::ConicGradient(ref gradient) => {
+            assert!((gradient.angle.unwrap().degrees() - 270.).abs() < 1e-4);
+            match gradient.items[0] {
+                GenericGradientItem::ColorStop(ref stop) => match stop.position {
+                    Some(Either::First(ref angle)) => assert!((angle.degrees() - 100.).abs() < 1e-4),
+                    _ => panic!("Expected an angle stop position"),
+                },
+                _ => panic!("Expected a color stop"),
+            }
+        },
+        _ => panic!("Expected a conic gradient"),
+    }
+
+    // calc() angles serialize as calc().
+    let image = parse_entirely(Image::parse, "conic-gradient(red calc(90deg), blue)").unwrap();
+    assert_eq!(image.to_css_string(), "conic-gradient(red calc(90deg), blue)");
+
+    // Lengths are not angles, even in calc().
+    assert!(parse_entirely(Image::parse, "conic-gradient(red calc(10px + 10deg), blue)").is_err());
+}
+
+#[test]
+fn test_cross_fade() {
+    assert_roundtrip_with_context!(Image::parse,
+                                   "cross-fade(url(a.png), url(b.png))",
+                                   "cross-fade(url(\"a.png\"), url(\"b.png\"))");
+    assert_roundtrip_with_context!(Image::parse,
+                                   "cross-fade(url(a.png), url(b.png) 40%)",
+                                   "cross-fade(url(\"a.png\"), url(\"b.png\") 40%)");
+    assert_roundtrip_with_context!(Image::parse,
+                                   "cross-fade(60% url(a.png), url(b.png) 40%)",
+                                   "cross-fade(url(\"a.png\") 60%, url(\"b.png\") 40%)");
+
+    // Nested images are parsed recursively.
+    assert_roundtrip_with_context!(Image::parse,
+                                   "cross-fade(linear-gradient(red, blue) 50%, url(b.png))",
+                                   "cross-fade(linear-gradient(red, blue) 50%, url(\"b.png\"))");
+
+    match parse_entirely(Image::parse, "cross-fade(url(a.png), url(b.png) 40%)").unwrap() {
+        GenericImage::CrossFade(ref cross_fade) => {
+            assert_eq!(cross_fade.elements.len(), 2);
+            assert!(cross_fade.elements[0].percentage.is_none());
+            assert_eq!(cross_fade.elements[1].percentage.map(|p| p.get()), Some(0.4));
+        },
+        _ => panic!("Expected a cross-fade"),
+    }
+
+    assert!(parse_entirely(Image::parse, "cross-fade()").is_err());
+    assert!(parse_entirely(Image::parse, "cross-fade(url(a.png) 10% 20%, url(b.png))").is_err());
+    assert!(parse_entirely(Image::parse, "cross-fade(url(a.png) 150%, url(b.png))").is_err());
+    assert!(parse_entirely(Image::parse, "cross-fade(url(a.png) -10%, url(b.png))").is_err());
+    assert!(parse_entirely(Image::parse, "cross-fade(50%, url(b.png))").is_err());
+}
+
+#[test]
+fn test_image_set() {
+    assert_roundtrip_with_context!(Image::parse,
+                                   "image-set(url(a.png) 1x, url(b.png) 2x)",
+                                   "image-set(url(\"a.png\") 1x, url(\"b.png\") 2x)");
+    assert_roundtrip_with_context!(Image::parse,
+                                   "image-set(url(a.png) 1dppx, url(b.png) 192dpi)",
+                                   "image-set(url(\"a.png\") 1dppx, url(\"b.png\") 192dpi)");
+    assert_roundtrip_with_context!(Image::parse,
+                                   "image-set(url(a.png) 2x type('image/webp'), url(b.png) 2x)",
+                                   "image-set(url(\"a.png\") 2x type(\"image/webp\"), url(\"b.png\") 2x)");
+
+    // The resolution defaults to 1x.
+    assert_roundtrip_with_context!(Image::parse,
+                                   "image-set(url(a.png))",
+                                   "image-set(url(\"a.png\") 1x)");
+
+    match parse_entirely(Image::parse, "image-set(url(a.png) 1x, url(b.png) 2dppx)").unwrap() {
+        GenericImage::ImageSet(ref image_set) => {
+            assert_eq!(image_set.items.len(), 2);
+            assert_eq!(image_set.items[0].resolution.to_dppx(), 1.);
+            assert_eq!(image_set.items[1].resolution.to_dppx(), 2.);
+            assert!(image_set.items[1].mime_type.is_none());
+        },
+        _ => panic!("Expected an image-set"),
+    }
+
+    assert!(parse_entirely(Image::parse, "image-set()").is_err());
+    assert!(parse_entirely(Image::parse, "image-set(url(a.png) 0x)").is_err());
+    assert!(parse_entirely(Image::parse, "image-set(url(a.png) 2px)").is_err());
+    assert!(parse_entirely(Image::parse, "image-set(url(a.png) 1x 2x)").is_err());
+    assert!(parse_entirely(Image::parse, "image-set(url(a.png) type(image/png))").is_err());
+}
+
+#[test]
+fn test_image_function() {
+    assert_roundtrip_with_context!(Image::parse,
+                                   "image(url(x.png), red)",
+                                   "image(url(\"x.png\"), red)");
+    assert_roundtrip_with_context!(Image::parse, "image(red)");
+    assert_roundtrip_with_context!(Image::parse, "image(url(x.png))", "image(url(\"x.png\"))");
+
+    match parse_entirely(Image::parse, "image(red)").unwrap() {
+        GenericImage::Image(ref image) => {
+            assert!(image.url.is_none());
+            assert!(image.color.is_some());
+        },
+        _ => panic!("Expected an image()"),
+    }
+
+    assert!(parse_entirely(Image::parse, "image()").is_err());
+    assert!(parse_entirely(Image::parse, "image(red, url(x.png))").is_err());
+    assert!(parse_entirely(Image::parse, "image(url(x.png) red)").is_err());
+    assert!(parse_entirely(Image::parse, "image(url(x.png),)").is_err());
+    assert!(parse_entirely(Image::parse, "image(url(x.png), red, blue)").is_err());
+}
+
+#[test]
+fn test_attr_image() {
+    assert_roundtrip_with_context!(Image::parse, "attr(data-img url)");
+    assert_roundtrip_with_context!(Image::parse, "attr(data-img   url)", "attr(data-img url)");
+    assert_roundtrip_with_context!(Image::parse, "attr(data-img URL)", "attr(data-img url)");
+
+    match parse_entirely(Image::parse, "attr(data-img url)").unwrap() {
+        GenericImage::Attr(ref attr) => {
+            assert_eq!(&*attr.attribute, "data-img");
+            assert!(attr.namespace.is_none());
+        },
+        _ => panic!("Expected an attr() image"),
+    }
+
+    // Only the `url` type makes sense for an image.
+    assert!(parse_entirely(Image::parse, "attr(data-img)").is_err());
+    assert!(parse_entirely(Image::parse, "attr(data-img string)").is_err());
+    assert!(parse_entirely(Image::parse, "attr(url)").is_err());
+    assert!(parse_entirely(Image::parse, "attr(data-img url url)").is_err());
+}
+
+#[test]
+fn test_url_image() {
+    let data = "data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNk+M9QDwADhgGAWjR9awAAAABJRU5ErkJggg==";
+    let input = format!("url({})", data);
+    let expected = format!("url(\"{}\")", data);
+
+    let mut parser_input = ::cssparser::ParserInput::new(&input);
+    parse_input(|context, i| {
+        let image = Image::parse(context, i).unwrap();
+        assert_eq!(image.to_css_string(), expected);
+        match image {
+            GenericImage::Url(ref url) => {
+                assert!(!url.is_invalid());
+                assert_eq!(url.url().unwrap().as_str(), data);
+            },
+            _ => panic!("Expected an url image"),
+        }
+        Ok(())
+    }, &mut parser_input).unwrap();
+
+    // Relative urls are resolved against the stylesheet's url.
+    let mut parser_input = ::cssparser::ParserInput::new("url(foo.png)");
+    parse_input(|context, i| {
+        match Image::parse(context, i).unwrap() {
+            GenericImage::Url(ref url) => {
+                assert_eq!(url.url().unwrap().as_str(), "http://localhost/foo.png");
+                assert_eq!(url.to_css_string(), "url(\"foo.png\")");
+            },
+            _ => panic!("Expected an url image"),
+        }
+        Ok(())
+    }, &mut parser_input).unwrap();
+
+    // Urls that fail to resolve make the image invalid.
+    assert!(parse_entirely(Image::parse, "url(http://[::1)").is_err());
+}
+
+#[test]
+fn test_image_layer() {
+    assert_roundtrip_with_context!(ImageLayer::parse, "none");
+    assert_roundtrip_with_context!(ImageLayer::parse, "None", "none");
+    assert_roundtrip_with_context!(ImageLayer::parse, "url(\"http://localhost/a.png\")");
+
+    match parse_entirely(ImageLayer::parse, "none").unwrap() {
+        Either::First(..) => {},
+        Either::Second(..) => panic!("Expected none"),
+    }
+
+    match parse_entirely(ImageLayer::parse, "url(a.png)").unwrap() {
+        Either::Second(GenericImage::Url(ref url)) => {
+            assert_eq!(url.url().unwrap().as_str(), "http://localhost/a.png");
+        },
+        _ => panic!("Expected an url image"),
+    }
+
+    // `none` is only valid on its own, and not as a plain image.
+    assert!(parse_entirely(Image::parse, "none").is_err());
+    assert!(parse_entirely(ImageLayer::parse, "none url(a.png)").is_err());
+}
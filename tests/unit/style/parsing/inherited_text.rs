@@ -42,3 +42,37 @@ fn line_height_should_return_length_on_length_zero() {
     let result = parse(line_height::parse, "0px").unwrap();
     assert_eq!(result, parse_longhand!(line_height, "0px"));
 }
+
+#[test]
+fn test_line_height() {
+    use style::parser::Parse;
+    use style::values::generics::text::LineHeight as GenericLineHeight;
+    use style::values::computed::Percentage;
+    use style::values::specified::{LengthOrPercentage, NoCalcLength};
+    use style::values::specified::text::LineHeight;
+    use style_traits::ToCss;
+
+    assert_eq!(parse(LineHeight::parse, "normal").unwrap(), GenericLineHeight::Normal);
+    match parse(LineHeight::parse, "1.5").unwrap() {
+        GenericLineHeight::Number(number) => assert_eq!(number.0.get(), 1.5),
+        other => panic!("Expected a number, got {:?}", other),
+    }
+    match parse(LineHeight::parse, "150%").unwrap() {
+        GenericLineHeight::Length(length) => {
+            assert_eq!(length.0, LengthOrPercentage::Percentage(Percentage(1.5)))
+        },
+        other => panic!("Expected a percentage, got {:?}", other),
+    }
+    match parse(LineHeight::parse, "20px").unwrap() {
+        GenericLineHeight::Length(length) => {
+            assert_eq!(length.0, LengthOrPercentage::Length(NoCalcLength::from_px(20.)))
+        },
+        other => panic!("Expected a length, got {:?}", other),
+    }
+
+    for value in &["normal", "1.5", "150%", "20px"] {
+        assert_roundtrip_with_context!(LineHeight::parse, *value);
+    }
+    assert!(parse(LineHeight::parse, "-1").is_err());
+    assert!(parse(LineHeight::parse, "-10px").is_err());
+}
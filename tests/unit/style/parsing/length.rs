@@ -2,12 +2,18 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use app_units::Au;
 use cssparser::{Parser, ParserInput};
 use parsing::parse;
 use style::context::QuirksMode;
 use style::parser::{Parse, ParserContext};
 use style::stylesheets::{CssRuleType, Origin};
-use style::values::specified::length::{AbsoluteLength, Length, NoCalcLength};
+use style::values::computed;
+use style::values::specified::{Angle, Color, Size};
+use style::values::specified::AllowQuirks;
+use style::values::specified::length::{AbsoluteLength, CharacterWidth, Length, LengthOrPercentage};
+use style::values::specified::length::{LengthOrPercentageOrAuto, LengthOrPercentageOrNone};
+use style::values::specified::length::NoCalcLength;
 use style_traits::{ParsingMode, ToCss};
 
 #[test]
@@ -20,6 +26,69 @@ fn test_calc() {
     assert!(parse(Length::parse, "calc( 1px + 2px / ( 1 + 2 - 1))").is_ok());
 }
 
+#[test]
+fn test_min_max() {
+    assert_roundtrip_with_context!(Length::parse, "min(10px, 2em)");
+    assert_roundtrip_with_context!(Length::parse, "max(10px)");
+    assert_roundtrip_with_context!(Length::parse, "clamp(1em, 5vw, 50px)");
+    assert_roundtrip_with_context!(Length::parse, "MAX( 10px , calc(2px + 1em) )", "max(10px, 1em + 2px)");
+    assert_roundtrip_with_context!(Length::parse, "min(10px * 2, 3em / 2)", "min(20px, 1.5em)");
+
+    // Arguments with the same percentage can be compared, and the percentage
+    // is deferred like in calc().
+    assert_roundtrip_with_context!(LengthOrPercentage::parse, "min(10px, 2em)");
+    assert_roundtrip_with_context!(LengthOrPercentage::parse, "max(10% + 5px, 10% + 2em)");
+    assert_roundtrip_with_context!(LengthOrPercentage::parse, "clamp(50%, 50%, 50%)");
+
+    assert!(parse(Length::parse, "min()").is_err());
+    assert!(parse(Length::parse, "min(10px 20px)").is_err());
+    assert!(parse(Length::parse, "min(10px, )").is_err());
+    assert!(parse(Length::parse, "min(10%, 20px)").is_err());
+    assert!(parse(Length::parse, "min(10, 20px)").is_err());
+    assert!(parse(Length::parse, "min(10deg, 20px)").is_err());
+    assert!(parse(Length::parse, "min(min(10px, 20px), 30px)").is_err());
+    assert!(parse(Length::parse, "clamp(1px, 2px)").is_err());
+    assert!(parse(Length::parse, "clamp(1px, 2px, 3px, 4px)").is_err());
+    assert!(parse(Length::parse, "minimum(1px, 2px)").is_err());
+
+    // Arguments with different percentages are compared at used value time.
+    assert_roundtrip_with_context!(LengthOrPercentage::parse, "min(10px, 50%)");
+    assert_roundtrip_with_context!(LengthOrPercentage::parse, "max(10% + 5px, 20% + 5px)");
+    assert_roundtrip_with_context!(LengthOrPercentage::parse, "clamp(100px, 50%, 20% + 1em)");
+    assert_roundtrip_with_context!(LengthOrPercentage::parse, "min(10px, 10%, 20%, 2em)");
+    assert!(parse(LengthOrPercentage::parse, "min(10px, 10%, 20%, 30%)").is_err());
+
+    // Properties that also take `auto` or `none` accept them too.
+    assert_roundtrip_with_context!(LengthOrPercentageOrAuto::parse, "min(10px, 50%)");
+    assert_roundtrip_with_context!(LengthOrPercentageOrAuto::parse, "clamp(1em, 50%, 200px)");
+    assert_roundtrip_with_context!(LengthOrPercentageOrNone::parse, "max(10px, 50%)");
+    assert!(parse(LengthOrPercentageOrAuto::parse, "min(auto, 10px)").is_err());
+    assert!(parse(LengthOrPercentageOrNone::parse, "max(none, 10px)").is_err());
+}
+
+#[test]
+fn test_stepped_value_functions() {
+    assert_roundtrip_with_context!(Length::parse, "round(up, 23px, 10px)");
+    assert_roundtrip_with_context!(Length::parse, "round(down, 2em, 3px)");
+    assert_roundtrip_with_context!(Length::parse, "round(to-zero, -23px, 1vw)");
+    assert_roundtrip_with_context!(Length::parse, "round(nearest, 23px, 10px)", "round(23px, 10px)");
+    assert_roundtrip_with_context!(Length::parse, "mod(18px, 5px)");
+    assert_roundtrip_with_context!(Length::parse, "REM( 18px , calc(2px + 1em) )", "rem(18px, 1em + 2px)");
+    assert_roundtrip_with_context!(LengthOrPercentage::parse, "mod(1em * 3, 10px / 2)", "mod(3em, 5px)");
+
+    assert!(parse(Length::parse, "round(23px)").is_err());
+    assert!(parse(Length::parse, "round(up, 23px)").is_err());
+    assert!(parse(Length::parse, "round(up 23px, 10px)").is_err());
+    assert!(parse(Length::parse, "round(sideways, 23px, 10px)").is_err());
+    assert!(parse(Length::parse, "round(23px, 10px, up)").is_err());
+    assert!(parse(Length::parse, "mod(up, 18px, 5px)").is_err());
+    assert!(parse(Length::parse, "mod(18px, 5px, 1px)").is_err());
+    assert!(parse(Length::parse, "mod(18px, 5)").is_err());
+    assert!(parse(Length::parse, "mod(18px, 5deg)").is_err());
+    assert!(parse(LengthOrPercentage::parse, "mod(50%, 5px)").is_err());
+    assert!(parse(LengthOrPercentage::parse, "round(23px, 10%)").is_err());
+}
+
 #[test]
 fn test_length_literals() {
     assert_roundtrip_with_context!(Length::parse, "0.33px", "0.33px");
@@ -31,6 +100,62 @@ fn test_length_literals() {
     assert_roundtrip_with_context!(Length::parse, "0.33pc", "0.33pc");
 }
 
+#[test]
+fn test_size() {
+    assert_roundtrip_with_context!(Size::parse, "auto", "auto");
+    assert_roundtrip_with_context!(Size::parse, "10px", "10px");
+    assert_roundtrip_with_context!(Size::parse, "min-content", "min-content");
+    assert_roundtrip_with_context!(Size::parse, "MAX-CONTENT", "max-content");
+    assert_roundtrip_with_context!(Size::parse, "fit-content(200px)", "fit-content(200px)");
+    assert_roundtrip_with_context!(Size::parse, "fit-content(50%)", "fit-content(50%)");
+
+    assert!(parse(Size::parse, "-10px").is_err());
+    assert!(parse(Size::parse, "fit-content").is_err());
+    assert!(parse(Size::parse, "fit-content(auto)").is_err());
+    assert!(parse(Size::parse, "fit-content(-1px)").is_err());
+}
+
+#[test]
+fn test_canonical_serialization() {
+    // Lengths keep their authored unit, without trailing zeros.
+    assert_roundtrip_with_context!(Length::parse, "1.0px", "1px");
+    assert_roundtrip_with_context!(Length::parse, "+2.50em", "2.5em");
+    assert_roundtrip_with_context!(Length::parse, "10.0in", "10in");
+    assert_roundtrip_with_context!(Length::parse, "0", "0px");
+
+    // Percentages don't get a trailing `.0`.
+    assert_roundtrip_with_context!(LengthOrPercentage::parse, "50.0%", "50%");
+    assert_roundtrip_with_context!(LengthOrPercentage::parse, "12.50%", "12.5%");
+    assert_roundtrip_with_context!(LengthOrPercentage::parse, "0", "0px");
+
+    assert_roundtrip_with_context!(Angle::parse, "90.0deg", "90deg");
+    assert_roundtrip_with_context!(Angle::parse, "0.50turn", "0.5turn");
+
+    assert_roundtrip_with_context!(Color::parse, "RED", "red");
+}
+
+#[test]
+fn test_character_width_serialization() {
+    // Character widths are only synthesized for legacy attributes, but
+    // serializing them (e.g. for debugging output) must not panic.
+    let width = NoCalcLength::ServoCharacterWidth(CharacterWidth(10));
+    assert_eq!(width.to_css_string(), "-servo-character-width(10)");
+    assert_eq!(Length::NoCalc(width).to_css_string(), "-servo-character-width(10)");
+}
+
+#[test]
+fn test_parse_str() {
+    let url = ::servo_url::ServoUrl::parse("http://localhost").unwrap();
+    let context = ParserContext::new(Origin::Author, &url,
+                                     Some(CssRuleType::Style), ParsingMode::DEFAULT,
+                                     QuirksMode::NoQuirks);
+    assert_eq!(Length::parse_str(&context, "10px").unwrap(), Length::from_px(10.));
+    assert!(Length::parse_str(&context, "10px 20px").is_err());
+    assert!(LengthOrPercentage::parse_str(&context, "50%").is_ok());
+    assert!(LengthOrPercentage::parse_str(&context, "50% ,").is_err());
+    assert_eq!(Color::parse_str(&context, "red").unwrap().to_css_string(), "red");
+}
+
 #[test]
 fn test_parsing_modes() {
     // In default length mode, non-zero lengths must have a unit.
@@ -47,3 +172,226 @@ fn test_parsing_modes() {
     assert!(result.is_ok());
     assert_eq!(result.unwrap(), Length::NoCalc(NoCalcLength::Absolute(AbsoluteLength::Px(1.))));
 }
+
+#[test]
+fn test_angle_unitless_zero() {
+    use parsing::parse_entirely;
+
+    assert_eq!(parse_entirely(Angle::parse, "0deg").unwrap(), Angle::zero());
+    assert_eq!(parse_entirely(Angle::parse_with_unitless, "0deg").unwrap(), Angle::zero());
+
+    // A unitless zero is only an angle where the grammar explicitly allows it.
+    assert!(parse_entirely(Angle::parse, "0").is_err());
+    assert_eq!(parse_entirely(Angle::parse_with_unitless, "0").unwrap(), Angle::zero());
+    assert_eq!(parse_entirely(Angle::parse_with_unitless, "0").unwrap().radians(), 0.);
+    assert_roundtrip_with_context!(Angle::parse_with_unitless, "0", "0deg");
+
+    // Other unitless numbers are never angles.
+    assert!(parse_entirely(Angle::parse_with_unitless, "1").is_err());
+    assert!(parse_entirely(Angle::parse_with_unitless, "-0.5").is_err());
+    assert!(parse_entirely(Angle::parse_with_unitless, "0px").is_err());
+}
+
+#[test]
+fn test_length_from_au() {
+    assert_eq!(NoCalcLength::from(Au::from_px(10)), NoCalcLength::from_px(10.));
+    assert_eq!(Length::from(Au::from_px(10)), Length::from_px(10.));
+    assert_eq!(Length::from(Au(30)), Length::from_px(0.5));
+
+    assert_eq!(computed::LengthOrPercentage::from(Au::from_px(10)),
+               computed::LengthOrPercentage::Length(computed::Length::new(10.)));
+}
+
+#[test]
+fn test_length_from_px_i32() {
+    assert_eq!(Length::from_px_i32(100), Length::from_px(100.0));
+    assert_eq!(Length::from_px_i32(0), Length::zero());
+    assert_eq!(Length::from_px_i32(-5), Length::from_px(-5.0));
+    assert_eq!(Length::from_px_i32(100).to_css_string(), "100px");
+}
+
+#[test]
+fn test_length_zero() {
+    assert!(Length::zero().is_zero());
+    assert_eq!(Length::zero(), Length::NoCalc(NoCalcLength::Absolute(AbsoluteLength::Px(0.))));
+    assert_eq!(parse(Length::parse, "0").unwrap(), Length::zero());
+
+    assert!(parse(Length::parse, "0px").unwrap().is_zero());
+    assert!(parse(Length::parse, "0in").unwrap().is_zero());
+    assert!(!parse(Length::parse, "1px").unwrap().is_zero());
+
+    // Relative lengths and calc() expressions aren't considered zero.
+    assert!(!parse(Length::parse, "0em").unwrap().is_zero());
+    assert!(!parse(Length::parse, "0vw").unwrap().is_zero());
+    assert!(!parse(Length::parse, "calc(0px)").unwrap().is_zero());
+}
+
+#[test]
+fn test_scientific_notation_and_signs() {
+    assert_eq!(parse(Length::parse, "1e2px").unwrap(), parse(Length::parse, "100px").unwrap());
+    assert_eq!(parse(Length::parse, "1.5E1px").unwrap(), parse(Length::parse, "15px").unwrap());
+    assert_eq!(parse(Length::parse, "+5px").unwrap(), parse(Length::parse, "5px").unwrap());
+    assert_eq!(parse(Length::parse, "-3px").unwrap().to_css_string(), "-3px");
+
+    assert!(parse(Length::parse_non_negative, "1e2px").is_ok());
+    assert!(parse(Length::parse_non_negative, "+5px").is_ok());
+    assert!(parse(Length::parse_non_negative, "-3px").is_err());
+}
+
+#[test]
+fn test_unitless_zero() {
+    assert_eq!(parse(Length::parse, "0").unwrap(), Length::from_px(0.));
+    assert_eq!(parse(LengthOrPercentage::parse, "0").unwrap(),
+               LengthOrPercentage::Length(NoCalcLength::from_px(0.)));
+    assert_eq!(parse(LengthOrPercentageOrAuto::parse, "0").unwrap(),
+               LengthOrPercentageOrAuto::Length(NoCalcLength::from_px(0.)));
+    assert_eq!(parse(LengthOrPercentageOrNone::parse, "0").unwrap(),
+               LengthOrPercentageOrNone::Length(NoCalcLength::from_px(0.)));
+
+    assert!(parse(Length::parse, "5").is_err());
+    assert!(parse(LengthOrPercentage::parse, "5").is_err());
+    assert!(parse(LengthOrPercentageOrAuto::parse, "5").is_err());
+    assert!(parse(LengthOrPercentageOrNone::parse, "5").is_err());
+
+    // The unitless length quirk lets non-zero numbers through in quirks mode.
+    let url = ::servo_url::ServoUrl::parse("http://localhost").unwrap();
+    let context = ParserContext::new(Origin::Author, &url, Some(CssRuleType::Style),
+                                     ParsingMode::DEFAULT, QuirksMode::Quirks);
+    let mut input = ParserInput::new("5");
+    let length = Length::parse_quirky(&context, &mut Parser::new(&mut input), AllowQuirks::Yes);
+    assert_eq!(length.unwrap(), Length::from_px(5.));
+    let mut input = ParserInput::new("5");
+    let lop = LengthOrPercentage::parse_quirky(&context, &mut Parser::new(&mut input), AllowQuirks::Yes);
+    assert_eq!(lop.unwrap(), LengthOrPercentage::Length(NoCalcLength::from_px(5.)));
+    let mut input = ParserInput::new("5");
+    let lopoa = LengthOrPercentageOrAuto::parse_quirky(&context, &mut Parser::new(&mut input),
+                                                       AllowQuirks::Yes);
+    assert_eq!(lopoa.unwrap(), LengthOrPercentageOrAuto::Length(NoCalcLength::from_px(5.)));
+    let mut input = ParserInput::new("5");
+    assert!(Length::parse_quirky(&context, &mut Parser::new(&mut input), AllowQuirks::No).is_err());
+}
+
+#[test]
+fn test_length_or_percentage_or_none_keyword() {
+    for &input in ["none", "NONE", "None", "nOnE"].iter() {
+        assert_eq!(parse(LengthOrPercentageOrNone::parse, input).unwrap(),
+                   LengthOrPercentageOrNone::None, "{}", input);
+    }
+    assert_roundtrip_with_context!(LengthOrPercentageOrNone::parse, "NONE", "none");
+
+    // The keyword is not a valid calc() or min() argument.
+    assert!(parse(LengthOrPercentageOrNone::parse, "calc(none)").is_err());
+    assert!(parse(LengthOrPercentageOrNone::parse, "calc(none + 10px)").is_err());
+    assert!(parse(LengthOrPercentage::parse, "min(none, 10px)").is_err());
+    assert!(parse(LengthOrPercentageOrNone::parse, "none none").is_err());
+}
+
+#[test]
+fn test_length_or_number() {
+    use style::values::Either;
+    use style::values::specified::{LengthOrNumber, Number};
+
+    assert_eq!(parse(LengthOrNumber::parse, "2px").unwrap(), Either::First(Length::from_px(2.)));
+    assert_eq!(parse(LengthOrNumber::parse, "3").unwrap(), Either::Second(Number::new(3.)));
+    assert_eq!(parse(LengthOrNumber::parse_non_negative, "2px").unwrap(),
+               Either::First(Length::from_px(2.)));
+    assert_eq!(parse(LengthOrNumber::parse_non_negative, "3").unwrap(),
+               Either::Second(Number::new(3.)));
+
+    // A bare zero is a length for the generic parser, but a number for the
+    // non-negative one.
+    assert_eq!(parse(LengthOrNumber::parse, "0").unwrap(), Either::First(Length::from_px(0.)));
+    assert_eq!(parse(LengthOrNumber::parse_non_negative, "0").unwrap(), LengthOrNumber::zero());
+    assert_roundtrip_with_context!(LengthOrNumber::parse, "0", "0px");
+    assert_roundtrip_with_context!(LengthOrNumber::parse_non_negative, "0");
+
+    assert_roundtrip_with_context!(LengthOrNumber::parse, "2px");
+    assert_roundtrip_with_context!(LengthOrNumber::parse, "3");
+    assert!(parse(LengthOrNumber::parse_non_negative, "-3").is_err());
+    assert!(parse(LengthOrNumber::parse_non_negative, "-2px").is_err());
+}
+
+#[test]
+fn test_length_is_font_relative() {
+    let length = |s| parse(Length::parse, s).unwrap();
+
+    assert!(length("1em").is_font_relative());
+    assert!(length("1ex").is_font_relative());
+    assert!(length("1ch").is_font_relative());
+    assert!(length("1rem").is_font_relative());
+    assert!(Length::NoCalc(NoCalcLength::ServoCharacterWidth(CharacterWidth(10))).is_font_relative());
+
+    assert!(!length("1px").is_font_relative());
+    assert!(!length("1in").is_font_relative());
+    assert!(!length("1vw").is_font_relative());
+    assert!(!Length::from(Au::from_px(10)).is_font_relative());
+
+    assert!(length("calc(1px + 1em)").is_font_relative());
+    assert!(!length("calc(1px + 1vh)").is_font_relative());
+    assert!(length("min(10px, 2rem)").is_font_relative());
+    assert!(!length("max(10px, 2vmin)").is_font_relative());
+}
+
+#[test]
+fn test_length_is_viewport_relative() {
+    let length = |s| parse(Length::parse, s).unwrap();
+
+    assert!(length("1vw").is_viewport_relative());
+    assert!(length("1vh").is_viewport_relative());
+    assert!(length("1vmin").is_viewport_relative());
+    assert!(length("1vmax").is_viewport_relative());
+
+    assert!(!length("1px").is_viewport_relative());
+    assert!(!length("1cm").is_viewport_relative());
+    assert!(!length("1em").is_viewport_relative());
+    assert!(!length("1rem").is_viewport_relative());
+    assert!(!Length::NoCalc(NoCalcLength::ServoCharacterWidth(CharacterWidth(10))).is_viewport_relative());
+    assert!(!Length::from(Au::from_px(10)).is_viewport_relative());
+
+    assert!(length("calc(1px + 1vw)").is_viewport_relative());
+    assert!(!length("calc(1px + 1em)").is_viewport_relative());
+    assert!(length("clamp(1em, 5vw, 50px)").is_viewport_relative());
+    assert!(!length("min(10px, 2rem)").is_viewport_relative());
+}
+
+#[test]
+fn test_constant_calc_is_kept_at_specified_value_time() {
+    // Constant expressions fold their terms, but stay calc() expressions, so
+    // that they serialize as calc() and keep their range clamping.
+    match parse(Length::parse, "calc(10px + 5px)").unwrap() {
+        Length::Calc(ref calc) => assert_eq!(calc.absolute, Some(AbsoluteLength::Px(15.))),
+        other => panic!("Expected a calc() expression, got {:?}", other),
+    }
+    assert_roundtrip_with_context!(Length::parse, "calc(10px + 5px)", "calc(15px)");
+    assert_roundtrip_with_context!(Length::parse, "calc(10px + 1em)", "calc(1em + 10px)");
+    assert_roundtrip_with_context!(Length::parse_non_negative, "calc(10px - 20px)", "calc(-10px)");
+
+    match parse(Length::parse, "calc(10px + 1em)").unwrap() {
+        Length::Calc(ref calc) => {
+            assert_eq!(calc.absolute, Some(AbsoluteLength::Px(10.)));
+            assert_eq!(calc.em, Some(1.));
+        },
+        other => panic!("Expected a calc() expression, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_calc_canonical_serialization() {
+    // Terms of the same unit are combined, and mixed absolute units are
+    // combined in pixels.
+    assert_roundtrip_with_context!(LengthOrPercentage::parse, "calc(1px + 2px + 3em)", "calc(3em + 3px)");
+    assert_roundtrip_with_context!(LengthOrPercentage::parse, "calc(2 * (1em + 1px) - 1px)", "calc(2em + 1px)");
+    assert_roundtrip_with_context!(LengthOrPercentage::parse, "calc(1in + 1in)", "calc(2in)");
+    assert_roundtrip_with_context!(LengthOrPercentage::parse, "calc(1in + 4px)", "calc(100px)");
+
+    // Percentages come first, and then the rest of the units in alphabetical
+    // order.
+    assert_roundtrip_with_context!(LengthOrPercentage::parse, "calc(10px + 50%)", "calc(50% + 10px)");
+    assert_roundtrip_with_context!(LengthOrPercentage::parse, "calc(10vw - 1rem + 5%)", "calc(5% - 1rem + 10vw)");
+    assert_roundtrip_with_context!(LengthOrPercentage::parse, "calc(1vh + 1ex + 1ch)", "calc(1ch + 1ex + 1vh)");
+
+    // Signs are folded into the operators.
+    assert_roundtrip_with_context!(LengthOrPercentage::parse, "calc(50% + -10px)", "calc(50% - 10px)");
+    assert_roundtrip_with_context!(LengthOrPercentage::parse, "calc(-1em)");
+    assert_roundtrip_with_context!(LengthOrPercentage::parse, "calc(-50% - 1px)");
+}
@@ -6,7 +6,7 @@
 
 use cssparser::{Parser, ParserInput};
 use style::context::QuirksMode;
-use style::parser::ParserContext;
+use style::parser::{Parse, ParserContext};
 use style::stylesheets::{CssRuleType, Origin};
 use style_traits::{ParsingMode, ParseError};
 
@@ -18,12 +18,28 @@ where F: for<'t> Fn(&ParserContext, &mut Parser<'static, 't>) -> Result<T, Parse
 
 fn parse_input<'i: 't, 't, T, F>(f: F, input: &'t mut ParserInput<'i>) -> Result<T, ParseError<'i>>
 where F: Fn(&ParserContext, &mut Parser<'i, 't>) -> Result<T, ParseError<'i>> {
-    let url = ::servo_url::ServoUrl::parse("http://localhost").unwrap();
+    with_parser_context("http://localhost", |context| {
+        let mut parser = Parser::new(input);
+        f(context, &mut parser)
+    })
+}
+
+/// Calls `f` with the author style rule context that value tests parse in,
+/// resolving urls against `url`.
+pub fn with_parser_context<F, R>(url: &str, f: F) -> R
+where F: FnOnce(&ParserContext) -> R {
+    let url = ::servo_url::ServoUrl::parse(url).unwrap();
     let context = ParserContext::new(Origin::Author, &url, Some(CssRuleType::Style),
                                      ParsingMode::DEFAULT,
                                      QuirksMode::NoQuirks);
-    let mut parser = Parser::new(input);
-    f(&context, &mut parser)
+    f(&context)
+}
+
+/// Parses a whole specified value from `s`, panicking on failure.
+pub fn parse_value<T: Parse>(s: &str) -> T {
+    with_parser_context("http://localhost", |context| {
+        T::parse_str(context, s).expect(&format!("Failed to parse {}", s))
+    })
 }
 
 fn parse_entirely<T, F>(f: F, s: &'static str) -> Result<T, ParseError<'static>>
@@ -107,16 +123,22 @@ mod animation;
 mod background;
 mod border;
 mod box_;
+mod color;
 mod column;
 mod effects;
+mod frequency;
+mod grid;
 mod image;
 mod inherited_text;
 mod length;
 mod outline;
 mod position;
+mod resolution;
 mod selectors;
 mod supports;
 mod text_overflow;
+mod time;
 mod transition_duration;
 mod transition_timing_function;
+mod url;
 mod value;
@@ -74,6 +74,29 @@ fn test_position() {
     assert!(parse(Position::parse, "x-start 20px y-end 10%").is_err());
 }
 
+#[test]
+fn test_single_value_position() {
+    // A single keyword fills its own axis, and the other one defaults to
+    // `center`.
+    assert_roundtrip_with_context!(Position::parse, "top", "center top");
+    assert_roundtrip_with_context!(Position::parse, "bottom", "center bottom");
+    assert_roundtrip_with_context!(Position::parse, "left", "left center");
+    assert_roundtrip_with_context!(Position::parse, "right", "right center");
+
+    // A single length or percentage is horizontal.
+    assert_roundtrip_with_context!(Position::parse, "10px", "10px center");
+    assert_roundtrip_with_context!(Position::parse, "25%", "25% center");
+
+    let top = parse_entirely(Position::parse, "top").unwrap();
+    assert_eq!(top.horizontal, PositionComponent::Center);
+    assert_eq!(top.vertical, PositionComponent::Side(Y::Top, None));
+    let left = parse_entirely(Position::parse, "left").unwrap();
+    assert_eq!(left.horizontal, PositionComponent::Side(X::Left, None));
+    assert_eq!(left.vertical, PositionComponent::Center);
+    let length = parse_entirely(Position::parse, "10px").unwrap();
+    assert_eq!(length, parse_entirely(Position::parse, "10px center").unwrap());
+}
+
 #[test]
 fn test_horizontal_position() {
     // One value serializations.
@@ -144,3 +167,19 @@ fn test_vertical_position() {
     assert!(parse(VerticalPosition::parse, "y-end").is_err());
 }
 
+
+#[test]
+fn test_non_negative_position_component() {
+    for &input in ["-10%", "-5px", "left -10%", "right -5px"].iter() {
+        assert!(parse_entirely(HorizontalPosition::parse, input).is_ok(), "{}", input);
+        assert!(parse_entirely(HorizontalPosition::parse_non_negative, input).is_err(), "{}", input);
+    }
+    assert!(parse_entirely(VerticalPosition::parse, "bottom -10%").is_ok());
+    assert!(parse_entirely(VerticalPosition::parse_non_negative, "bottom -10%").is_err());
+
+    assert_roundtrip_with_context!(HorizontalPosition::parse_non_negative, "center");
+    assert_roundtrip_with_context!(HorizontalPosition::parse_non_negative, "10%");
+    assert_roundtrip_with_context!(HorizontalPosition::parse_non_negative, "0px");
+    assert_roundtrip_with_context!(HorizontalPosition::parse_non_negative, "right 10px");
+    assert_roundtrip_with_context!(VerticalPosition::parse_non_negative, "top 25%");
+}
@@ -0,0 +1,29 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use parsing::parse_entirely;
+use style::parser::Parse;
+use style::values::specified::Resolution;
+use style_traits::ToCss;
+
+#[test]
+fn test_resolution() {
+    assert_roundtrip_with_context!(Resolution::parse, "96dpi");
+    assert_roundtrip_with_context!(Resolution::parse, "2dppx");
+    assert_roundtrip_with_context!(Resolution::parse, "2x");
+    assert_roundtrip_with_context!(Resolution::parse, "2.54dpcm");
+
+    let dpi = parse_entirely(Resolution::parse, "96dpi").unwrap();
+    assert_eq!(dpi.to_dppx(), 1.);
+
+    // One dot per centimeter is 2.54 dots per inch.
+    let dpcm = parse_entirely(Resolution::parse, "1dpcm").unwrap();
+    assert_eq!(dpcm.to_dpi(), 2.54);
+
+    // Resolutions must be positive, and need a unit.
+    assert!(parse_entirely(Resolution::parse, "0dppx").is_err());
+    assert!(parse_entirely(Resolution::parse, "-1dpi").is_err());
+    assert!(parse_entirely(Resolution::parse, "96").is_err());
+    assert!(parse_entirely(Resolution::parse, "96px").is_err());
+}
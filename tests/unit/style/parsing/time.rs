@@ -0,0 +1,31 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use parsing::parse_entirely;
+use style::parser::Parse;
+use style::values::specified::Time;
+use style_traits::ToCss;
+
+#[test]
+fn test_time() {
+    assert_roundtrip_with_context!(Time::parse, "200ms");
+    assert_roundtrip_with_context!(Time::parse, "1.5s");
+    assert_roundtrip_with_context!(Time::parse, "-1s");
+
+    assert_eq!(parse_entirely(Time::parse, "200ms").unwrap().seconds(), 0.2);
+    assert_eq!(parse_entirely(Time::parse, "1.5s").unwrap().seconds(), 1.5);
+
+    // Times need a unit, even when zero.
+    assert!(parse_entirely(Time::parse, "0").is_err());
+    assert!(parse_entirely(Time::parse, "1.5").is_err());
+    assert!(parse_entirely(Time::parse, "1.5px").is_err());
+}
+
+#[test]
+fn test_non_negative_time() {
+    assert!(parse_entirely(Time::parse_non_negative, "0s").is_ok());
+    assert!(parse_entirely(Time::parse_non_negative, "200ms").is_ok());
+    assert!(parse_entirely(Time::parse_non_negative, "-1s").is_err());
+    assert!(parse_entirely(Time::parse_non_negative, "calc(-1s)").is_err());
+}
@@ -0,0 +1,44 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use parsing::with_parser_context;
+use style::parser::Parse;
+use style::values::CssUrl;
+use style_traits::ToCss;
+
+fn parse_url(base: &str, input: &str) -> CssUrl {
+    with_parser_context(base, |context| CssUrl::parse_str(context, input).unwrap())
+}
+
+#[test]
+fn test_fragment_url() {
+    let url = parse_url("http://servo.org/styles/main.css", "url(#clip)");
+    assert!(url.is_fragment());
+    assert_eq!(url.as_str(), "http://servo.org/styles/main.css#clip");
+    assert_eq!(url.url().unwrap().fragment(), Some("clip"));
+    assert_eq!(url.to_css_string(), "url(\"#clip\")");
+}
+
+#[test]
+fn test_url_with_fragment() {
+    let url = parse_url("http://servo.org/styles/main.css", "url(sprite.svg#icon)");
+    assert!(!url.is_fragment());
+    assert_eq!(url.as_str(), "http://servo.org/styles/sprite.svg#icon");
+    assert_eq!(url.url().unwrap().fragment(), Some("icon"));
+    assert_eq!(url.to_css_string(), "url(\"sprite.svg#icon\")");
+
+    let url = parse_url("http://servo.org/styles/main.css", "url(\"../img/sprite.svg#icon\")");
+    assert_eq!(url.as_str(), "http://servo.org/img/sprite.svg#icon");
+}
+
+#[test]
+fn test_url_with_query() {
+    let url = parse_url("http://servo.org/styles/main.css?v=1", "url(\"image.png?size=2&format=webp\")");
+    assert!(!url.is_fragment());
+    assert_eq!(url.as_str(), "http://servo.org/styles/image.png?size=2&format=webp");
+    assert_eq!(url.url().unwrap().query(), Some("size=2&format=webp"));
+
+    let url = parse_url("http://servo.org/styles/main.css", "url(\"image.png?size=2#part\")");
+    assert_eq!(url.as_str(), "http://servo.org/styles/image.png?size=2#part");
+}
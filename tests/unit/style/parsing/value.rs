@@ -3,11 +3,12 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use cssparser::{Parser, ParserInput};
+use parsing::parse_entirely;
 use style::context::QuirksMode;
-use style::parser::ParserContext;
+use style::parser::{Parse, ParserContext};
 use style::stylesheets::{CssRuleType, Origin};
-use style::values::specified::Number;
-use style_traits::ParsingMode;
+use style::values::specified::{Integer, Number, NumberOrPercentage, Percentage};
+use style_traits::{ParsingMode, ToCss};
 
 #[test]
 fn test_parsing_allo_all_numeric_values() {
@@ -23,3 +24,85 @@ fn test_parsing_allo_all_numeric_values() {
     assert_eq!(result.unwrap(), Number::new(-1.));
 }
 
+
+#[test]
+fn test_number() {
+    assert_eq!(parse_entirely(Number::parse, "1.5").unwrap(), Number::new(1.5));
+    assert_eq!(parse_entirely(Number::parse, "-2").unwrap(), Number::new(-2.));
+    assert!(parse_entirely(Number::parse, "calc(1 + 2)").is_ok());
+    assert!(parse_entirely(Number::parse, "1px").is_err());
+    assert!(parse_entirely(Number::parse, "50%").is_err());
+
+    assert!(parse_entirely(Number::parse_non_negative, "0").is_ok());
+    assert!(parse_entirely(Number::parse_non_negative, "-1").is_err());
+}
+
+#[test]
+fn test_integer() {
+    assert_eq!(parse_entirely(Integer::parse, "3").unwrap(), Integer::new(3));
+    assert_eq!(parse_entirely(Integer::parse, "-3").unwrap(), Integer::new(-3));
+    assert!(parse_entirely(Integer::parse, "1.5").is_err());
+    assert!(parse_entirely(Integer::parse, "1px").is_err());
+    assert!(parse_entirely(Integer::parse, "50%").is_err());
+
+    assert!(parse_entirely(Integer::parse_non_negative, "0").is_ok());
+    assert!(parse_entirely(Integer::parse_non_negative, "-1").is_err());
+    assert!(parse_entirely(Integer::parse_positive, "1").is_ok());
+    assert!(parse_entirely(Integer::parse_positive, "0").is_err());
+}
+
+#[test]
+fn test_percentage() {
+    // Percentages are stored normalized to [0 .. 1].
+    let percentage = parse_entirely(Percentage::parse, "50%").unwrap();
+    assert_eq!(percentage.get(), 0.5);
+    assert_eq!(percentage, Percentage::new(0.5));
+    assert_roundtrip_with_context!(Percentage::parse, "50%");
+    assert_roundtrip_with_context!(Percentage::parse, "-25%");
+    assert_roundtrip_with_context!(Percentage::parse, "150%");
+    assert!(parse_entirely(Percentage::parse, "0.5").is_err());
+    assert!(parse_entirely(Percentage::parse, "50px").is_err());
+
+    assert!(parse_entirely(Percentage::parse_non_negative, "0%").is_ok());
+    assert!(parse_entirely(Percentage::parse_non_negative, "-1%").is_err());
+}
+
+#[test]
+fn test_number_or_percentage() {
+    assert_eq!(parse_entirely(NumberOrPercentage::parse, "0.5").unwrap(),
+               NumberOrPercentage::Number(Number::new(0.5)));
+    assert_eq!(parse_entirely(NumberOrPercentage::parse, "50%").unwrap(),
+               NumberOrPercentage::Percentage(Percentage::new(0.5)));
+    assert_roundtrip_with_context!(NumberOrPercentage::parse, "0.5");
+    assert_roundtrip_with_context!(NumberOrPercentage::parse, "50%");
+    assert_roundtrip_with_context!(NumberOrPercentage::parse, "-1");
+    assert!(parse_entirely(NumberOrPercentage::parse, "50px").is_err());
+
+    assert!(parse_entirely(NumberOrPercentage::parse_non_negative, "0").is_ok());
+    assert!(parse_entirely(NumberOrPercentage::parse_non_negative, "-1").is_err());
+    assert!(parse_entirely(NumberOrPercentage::parse_non_negative, "-1%").is_err());
+}
+
+#[test]
+fn test_four_sides() {
+    use style::properties::shorthands::margin;
+    use style::values::generics::rect::Rect;
+    use style::values::specified::{LengthOrPercentageOrAuto, NoCalcLength};
+
+    let px = |v| LengthOrPercentageOrAuto::Length(NoCalcLength::from_px(v));
+    let parse_rect = |s| parse_entirely(|c, i| Rect::parse_with(c, i, LengthOrPercentageOrAuto::parse), s);
+
+    assert_eq!(parse_rect("10px").unwrap(), Rect::new(px(10.), px(10.), px(10.), px(10.)));
+    assert_eq!(parse_rect("10px 20px").unwrap(), Rect::new(px(10.), px(20.), px(10.), px(20.)));
+    assert_eq!(parse_rect("10px 20px 30px").unwrap(), Rect::new(px(10.), px(20.), px(30.), px(20.)));
+    assert_eq!(parse_rect("10px 20px 30px 40px").unwrap(), Rect::new(px(10.), px(20.), px(30.), px(40.)));
+    assert!(parse_rect("10px 20px 30px 40px 50px").is_err());
+    assert!(parse_rect("").is_err());
+
+    // Shorthands like margin expand to top, right, bottom and left.
+    let result = parse_entirely(margin::parse_value, "10px auto 30px").unwrap();
+    assert_eq!(result.margin_top, px(10.));
+    assert_eq!(result.margin_right, LengthOrPercentageOrAuto::Auto);
+    assert_eq!(result.margin_bottom, px(30.));
+    assert_eq!(result.margin_left, LengthOrPercentageOrAuto::Auto);
+}
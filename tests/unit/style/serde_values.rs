@@ -0,0 +1,63 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use cssparser::RGBA;
+use serde_json;
+use parsing::parse_value;
+use style::values::computed;
+use style::values::specified::{Color, Length, LengthOrPercentage, LengthOrPercentageOrAuto};
+use style::values::specified::{LengthOrPercentageOrNone, Percentage};
+use style::values::specified::image::Image;
+use style_traits::ToCss;
+
+macro_rules! assert_serde_roundtrip {
+    ($value:expr) => {{
+        let value = $value;
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(serde_json::from_str(&json).ok(), Some(value), "{}", json);
+    }}
+}
+
+#[test]
+fn test_specified_values_serde_roundtrip() {
+    assert_serde_roundtrip!(parse_value::<Length>("10px"));
+    assert_serde_roundtrip!(parse_value::<Length>("2.5em"));
+    assert_serde_roundtrip!(parse_value::<Length>("calc(1vw + 2rem)"));
+    assert_serde_roundtrip!(parse_value::<LengthOrPercentage>("50%"));
+    assert_serde_roundtrip!(parse_value::<LengthOrPercentage>("calc(50% - 1px)"));
+    assert_serde_roundtrip!(parse_value::<LengthOrPercentageOrAuto>("auto"));
+    assert_serde_roundtrip!(parse_value::<LengthOrPercentageOrNone>("none"));
+    assert_serde_roundtrip!(parse_value::<Percentage>("12.5%"));
+    assert_serde_roundtrip!(parse_value::<Color>("currentcolor"));
+    assert_serde_roundtrip!(parse_value::<Color>("#ff000080"));
+}
+
+#[test]
+fn test_specified_image_serde_roundtrip() {
+    for &input in ["url(\"http://example.com/a.png\")",
+                   "linear-gradient(to left top, red, blue 50%)",
+                   "repeating-radial-gradient(circle closest-side at 10px 20%, red, blue)",
+                   "conic-gradient(from 45deg, red, blue)",
+                   "cross-fade(url(\"a.png\") 25%, red)",
+                   "image-set(url(\"a.png\") 1x, url(\"b.png\") 2x)"].iter() {
+        let image = parse_value::<Image>(input);
+        let json = serde_json::to_string(&image).unwrap();
+        let deserialized: Image = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.to_css_string(), image.to_css_string());
+    }
+}
+
+#[test]
+fn test_computed_values_serde_roundtrip() {
+    let calc = computed::CalcLengthOrPercentage::new(computed::Length::new(-10.),
+                                                     Some(computed::Percentage(1.)));
+    assert_serde_roundtrip!(computed::Length::new(10.));
+    assert_serde_roundtrip!(computed::LengthOrPercentage::Length(computed::Length::new(10.)));
+    assert_serde_roundtrip!(computed::LengthOrPercentage::Percentage(computed::Percentage(0.5)));
+    assert_serde_roundtrip!(computed::LengthOrPercentage::Calc(calc));
+    assert_serde_roundtrip!(computed::LengthOrPercentageOrAuto::Auto);
+    assert_serde_roundtrip!(computed::LengthOrPercentageOrNone::None);
+    assert_serde_roundtrip!(computed::Color::rgba(RGBA::new(255, 0, 0, 128)));
+    assert_serde_roundtrip!(computed::Color::currentcolor());
+}
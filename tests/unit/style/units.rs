@@ -0,0 +1,58 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use app_units::{Au, MAX_AU, MIN_AU};
+use parsing::with_parser_context;
+use style::values::specified::length::NoCalcLength;
+use style::values::units::*;
+
+/// Parses `value` with `unit` the same way the `<length>` parser does, and
+/// converts the result to app units.
+fn parse_dimension_to_au(value: f32, unit: &str) -> Au {
+    let length = with_parser_context("http://localhost", |context| {
+        NoCalcLength::parse_dimension(context, value, unit).unwrap()
+    });
+    match length {
+        NoCalcLength::Absolute(length) => Au::from_f32_px(length.to_px()),
+        length => panic!("{:?} is not an absolute length", length),
+    }
+}
+
+#[test]
+fn test_constants() {
+    assert_eq!(AU_PER_PX, 60.);
+    assert_eq!(AU_PER_IN, 96. * AU_PER_PX);
+    assert_eq!(AU_PER_PT * 72., AU_PER_IN);
+    assert_eq!(AU_PER_PC, 12. * AU_PER_PT);
+}
+
+#[test]
+fn test_conversions_match_parsing() {
+    for &value in &[0., 1., 2.5, 12., -3.] {
+        let v = value as f64;
+        assert_eq!(px_to_au(v), parse_dimension_to_au(value, "px"), "{}px", value);
+        assert_eq!(in_to_au(v), parse_dimension_to_au(value, "in"), "{}in", value);
+        assert_eq!(cm_to_au(v), parse_dimension_to_au(value, "cm"), "{}cm", value);
+        assert_eq!(mm_to_au(v), parse_dimension_to_au(value, "mm"), "{}mm", value);
+        assert_eq!(q_to_au(v), parse_dimension_to_au(value, "q"), "{}q", value);
+        assert_eq!(pt_to_au(v), parse_dimension_to_au(value, "pt"), "{}pt", value);
+        assert_eq!(pc_to_au(v), parse_dimension_to_au(value, "pc"), "{}pc", value);
+    }
+}
+
+#[test]
+fn test_px_round_trip() {
+    assert_eq!(px_to_au(1.), Au(60));
+    assert_eq!(au_to_px(Au(60)), 1.);
+    assert_eq!(au_to_px(Au(30)), 0.5);
+    assert_eq!(au_to_px(px_to_au(123.5)), 123.5);
+    assert_eq!(pt_to_au(72.), in_to_au(1.));
+    assert_eq!(pt_to_au(72.), px_to_au(96.));
+}
+
+#[test]
+fn test_conversions_saturate() {
+    assert_eq!(px_to_au(1e20), MAX_AU);
+    assert_eq!(in_to_au(-1e20), MIN_AU);
+}